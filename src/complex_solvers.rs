@@ -0,0 +1,115 @@
+extern crate nalgebra as na;
+
+use na::{Complex, ComplexField};
+
+use crate::solver_error::SolverError;
+use crate::solver_result::{SolverResult, TerminationReason};
+
+// Solvers for complex functions f(z) = 0 of a complex variable. The value f_x of the reports is the modulus of f at the solution.
+
+fn is_finite(z: Complex<f64>) -> bool {
+    z.re.is_finite() && z.im.is_finite()
+}
+
+/// @brief Newton's method for solving a complex function f(z) = 0
+/// @param f function to solve, which must be analytic
+/// @param df derivative of function f
+/// @param z0 initial guess
+/// @param tol tolerance on the modulus of the step
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note Starting from a real guess, the iterates of a function that is real on the real axis stay real : use a guess with a non-zero imaginary part to find complex roots.
+pub fn complex_newton_solve<F, F2>(f : F, df : F2, z0 : Complex<f64>, tol : f64, max_iter : u32) -> Result<SolverResult<Complex<f64>>, SolverError>
+    where F : Fn(Complex<f64>) -> Complex<f64>, F2 : Fn(Complex<f64>) -> Complex<f64>
+{
+    let mut z: Complex<f64> = z0;
+    let mut dz: Complex<f64>;
+    let mut fz: Complex<f64> = f(z);
+    let mut dfz: Complex<f64>;
+    let mut n_fev: u32 = 1;
+    for iter in 0..max_iter {
+        if !is_finite(fz) {
+            return Err(SolverError::NonFiniteValue);
+        }
+        dfz = df(z);
+        if dfz.norm_sqr() == 0.0 {
+            return Err(SolverError::ZeroDerivative);
+        }
+        dz = fz/dfz;
+        z -= dz;
+        fz = f(z); n_fev += 1;
+        if dz.norm_sqr().sqrt() < tol {
+            return Ok(SolverResult::new(z, fz.norm_sqr().sqrt(), iter + 1, n_fev, TerminationReason::StepTolerance));
+        }
+    }
+    Err(SolverError::MaxIterations)
+}
+
+/// @brief Newton's method for solving a complex function f(z) = 0
+/// @param f function to solve, which must be analytic
+/// @param z0 initial guess
+/// @param tol tolerance on the modulus of the step
+/// @param dx_num numerical differentiation step size
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note This method uses central finite-differences along the real axis to compute the derivative, which is the same in every direction for an analytic function.
+pub fn complex_newton_solve_num<F>(f : F, z0 : Complex<f64>, tol : f64, dx_num : f64, max_iter : u32) -> Result<SolverResult<Complex<f64>>, SolverError>
+where F : Fn(Complex<f64>) -> Complex<f64>
+{
+    let mut res: SolverResult<Complex<f64>> = complex_newton_solve(&f, |z: Complex<f64>| {
+        (f(z + dx_num) - f(z - dx_num))/(2.0*dx_num)
+    }, z0, tol, max_iter)?;
+    res.n_fev += 2*res.n_iter;// Two evaluations per derivative
+    Ok(res)
+}
+
+/// @brief Muller's method for solving a complex function f(z) = 0
+/// @param f function to solve
+/// @param z0 first initial point
+/// @param z1 second initial point
+/// @param z2 third initial point, the closest to the root
+/// @param tol tolerance on the modulus of the step
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note Each iteration moves to the root of the parabola interpolating the last three points that is closest to the last one. The method converges with order 1.84 without requiring derivatives.
+/// @note The square root of the discriminant may be complex, so complex roots are found even from real starting points.
+pub fn muller_solve<F>(f : F, z0 : Complex<f64>, z1 : Complex<f64>, z2 : Complex<f64>, tol : f64, max_iter : u32) -> Result<SolverResult<Complex<f64>>, SolverError>
+where F : Fn(Complex<f64>) -> Complex<f64>
+{
+    let (mut z0, mut z1, mut z2) = (z0, z1, z2);
+    let (mut f0, mut f1, mut f2) = (f(z0), f(z1), f(z2));
+    let mut n_fev: u32 = 3;
+    if z0 == z1 || z1 == z2 || z0 == z2 {
+        return Err(SolverError::InvalidInput("the three initial points must be different"));
+    }
+    for iter in 0..max_iter {
+        if !is_finite(f0) || !is_finite(f1) || !is_finite(f2) {
+            return Err(SolverError::NonFiniteValue);
+        }
+        if f2.norm_sqr() == 0.0 {
+            return Ok(SolverResult::new(z2, 0.0, iter, n_fev, TerminationReason::ExactRoot));
+        }
+        // Parabola a*(z - z2)^2 + b*(z - z2) + c through the three points
+        let h1: Complex<f64> = z1 - z0;
+        let h2: Complex<f64> = z2 - z1;
+        let d1: Complex<f64> = (f1 - f0)/h1;
+        let d2: Complex<f64> = (f2 - f1)/h2;
+        let a: Complex<f64> = (d2 - d1)/(h2 + h1);
+        let b: Complex<f64> = a*h2 + d2;
+        let c: Complex<f64> = f2;
+        let disc: Complex<f64> = (b*b - 4.0*a*c).sqrt();
+        // The denominator of largest modulus gives the root closest to z2, and avoids cancellation
+        let den: Complex<f64> = if (b + disc).norm_sqr() >= (b - disc).norm_sqr() { b + disc } else { b - disc };
+        if den.norm_sqr() == 0.0 {
+            return Err(SolverError::ZeroDerivative);
+        }
+        let dz: Complex<f64> = -2.0*c/den;
+        (z0, z1, z2) = (z1, z2, z2 + dz);
+        (f0, f1) = (f1, f2);
+        f2 = f(z2); n_fev += 1;
+        if dz.norm_sqr().sqrt() < tol {
+            return Ok(SolverResult::new(z2, f2.norm_sqr().sqrt(), iter + 1, n_fev, TerminationReason::StepTolerance));
+        }
+    }
+    Err(SolverError::MaxIterations)
+}
@@ -0,0 +1,144 @@
+extern crate nalgebra as na;
+
+use std::cell::Cell;
+
+use crate::finite_differences;
+use crate::line_search;
+use crate::solver_error::SolverError;
+use crate::solver_result::{SolverResult, TerminationReason};
+
+/// Formula of the coefficient beta used to build the conjugate search directions p_k+1 = -g_k+1 + beta*p_k.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConjugateGradientBeta {
+    FletcherReeves,  // beta = |g_k+1|^2 / |g_k|^2
+    PolakRibierePlus,// beta = max(0, g_k+1^T*(g_k+1 - g_k) / |g_k|^2)
+    HagerZhang,      // beta of Hager & Zhang (2005), bounded below to ensure descent
+}
+
+/// Computes the coefficient beta of the conjugate direction.
+/// @param method formula of beta
+/// @param g_new gradient at the new point
+/// @param g_old gradient at the previous point
+/// @param p_old previous search direction
+/// @return beta
+fn conjugate_gradient_beta(method: ConjugateGradientBeta, g_new: &na::DVector<f64>, g_old: &na::DVector<f64>, p_old: &na::DVector<f64>) -> f64 {
+    let y: na::DVector<f64> = g_new - g_old;
+    match method {
+        ConjugateGradientBeta::FletcherReeves => {
+            g_new.norm_squared()/g_old.norm_squared()
+        },
+        ConjugateGradientBeta::PolakRibierePlus => {
+            f64::max(0.0, g_new.dot(&y)/g_old.norm_squared())
+        },
+        ConjugateGradientBeta::HagerZhang => {
+            let eta: f64 = 0.01;
+            let p_t_y: f64 = p_old.dot(&y);
+            let beta: f64 = (&y - (2.0*y.norm_squared()/p_t_y)*p_old).dot(g_new)/p_t_y;
+            let beta_min: f64 = -1.0/(p_old.norm()*f64::min(eta, g_old.norm()));
+            f64::max(beta, beta_min)
+        },
+    }
+}
+
+/// Non-linear conjugate gradient method for minimizing a function f(x)
+/// @param f function to minimize
+/// @param grad gradient of f
+/// @param x0 initial guess
+/// @param method formula of the coefficient beta (Fletcher-Reeves, Polak-Ribiere+ or Hager-Zhang)
+/// @param tol tolerance on the norm of the gradient and on the norm of the step
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note The method only stores a few vectors, which makes it suitable for problems with many variables. It usually requires more iterations than the quasi-Newton methods.
+/// @note The search direction is restarted along the steepest descent every n iterations, when successive gradients are far from orthogonal (Powell's criterion), or when it is not a descent direction.
+/// @note Steps are computed with a line search satisfying the strong Wolfe conditions. If the line search fails along the steepest descent, the current iterate is returned with termination = LineSearchFailure and converged = false.
+pub fn conjugate_gradient_minimize<F, G>(f: F, grad: G, x0: &na::DVector<f64>, method: ConjugateGradientBeta, tol: f64, max_iter: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError>
+where F : Fn(&na::DVector<f64>) -> f64, G : Fn(&na::DVector<f64>) -> na::DVector<f64>
+{
+    // Parameters
+    let c1: f64 = 1e-4;            // Sufficient decrease coefficient of the line search
+    let c2: f64 = 0.1;             // Curvature coefficient of the line search (must be lower than 0.5 for Fletcher-Reeves)
+    let max_iter_line_search: u32 = 30;
+    let restart_orthogonality: f64 = 0.2;// Powell's restart criterion |g_k+1^T*g_k| >= nu*|g_k+1|^2
+
+    let n_dims: usize = x0.len();
+    let mut x: na::DVector<f64> = x0.clone();
+    let mut f_x: f64 = f(&x);
+    let mut g_x: na::DVector<f64> = grad(&x);
+    let mut n_fev: u32 = 1;
+    if g_x.len() != n_dims {
+        return Err(SolverError::InvalidInput("the gradient must have as many components as x"));
+    }
+    let mut p: na::DVector<f64> = -&g_x;
+    let mut alpha: f64 = f64::min(1.0, 1.0/g_x.norm());// The first step length does not exceed 1
+    let mut n_since_restart: usize = 0;
+
+    for iter in 0..max_iter {
+        if !f_x.is_finite() || g_x.iter().any(|gi| !gi.is_finite()) {
+            return Err(SolverError::NonFiniteValue);
+        }
+        if g_x.amax() < tol {
+            return Ok(SolverResult::new(x, f_x, iter, n_fev, TerminationReason::GradientTolerance));
+        }
+
+        let dphi0: f64 = g_x.dot(&p);
+        let point = match line_search::strong_wolfe_line_search(&f, &grad, &x, f_x, &g_x, &p, alpha, c1, c2, max_iter_line_search, &mut n_fev) {
+            Ok(point) => point,
+            Err(_) if n_since_restart > 0 => {
+                // The line search failed along the conjugate direction : restart along the steepest descent
+                p = -&g_x;
+                alpha = f64::min(1.0, 1.0/g_x.norm());
+                n_since_restart = 0;
+                continue;
+            },
+            // The line search also failed along the steepest descent (usually close to a minimum, at the limit of the machine precision) : x is the best point found
+            Err(_) => return Ok(SolverResult::new(x, f_x, iter, n_fev, TerminationReason::LineSearchFailure)),
+        };
+        let s: na::DVector<f64> = point.alpha*&p;
+        x = point.x;
+        f_x = point.f_x;
+        let g_old: na::DVector<f64> = std::mem::replace(&mut g_x, point.grad);
+
+        if s.norm() < tol {
+            return Ok(SolverResult::new(x, f_x, iter + 1, n_fev, TerminationReason::StepTolerance));
+        }
+
+        // New conjugate direction, or restart along the steepest descent
+        n_since_restart += 1;
+        let restart: bool = n_since_restart >= n_dims || g_x.dot(&g_old).abs() >= restart_orthogonality*g_x.norm_squared();
+        let beta: f64 = if restart { 0.0 } else { conjugate_gradient_beta(method, &g_x, &g_old, &p) };
+        let mut p_new: na::DVector<f64> = -&g_x + beta*&p;
+        if restart || !beta.is_finite() || g_x.dot(&p_new) >= 0.0 {
+            p_new = -&g_x;
+            n_since_restart = 0;
+        }
+
+        // Initial step length of the next line search, assuming the first-order change of f is the same as in the previous iteration (Nocedal & Wright, eq. 3.60)
+        alpha = point.alpha*dphi0/g_x.dot(&p_new);
+        if !alpha.is_finite() || alpha <= 0.0 {
+            alpha = 1.0;
+        }
+        p = p_new;
+    }
+    Ok(SolverResult::new(x, f_x, max_iter, n_fev, TerminationReason::MaxIterations))
+}
+
+/// Non-linear conjugate gradient method for minimizing a function f(x)
+/// @param f function to minimize
+/// @param x0 initial guess
+/// @param method formula of the coefficient beta (Fletcher-Reeves, Polak-Ribiere+ or Hager-Zhang)
+/// @param tol tolerance on the norm of the gradient and on the norm of the step
+/// @param dx_num numerical differentiation step size
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note This method uses central finite-differences to compute the gradient.
+pub fn conjugate_gradient_minimize_num<F>(f: F, x0: &na::DVector<f64>, method: ConjugateGradientBeta, tol: f64, dx_num: f64, max_iter: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError>
+where F : Fn(&na::DVector<f64>) -> f64
+{
+    let n_grad: Cell<u32> = Cell::new(0);
+    let mut res: SolverResult<na::DVector<f64>> = conjugate_gradient_minimize(&f, |x: &na::DVector<f64>| {
+        n_grad.set(n_grad.get() + 1);
+        finite_differences::gradient_num(&f, x, dx_num)
+    }, x0, method, tol, max_iter)?;
+    res.n_fev += 2*(x0.len() as u32)*n_grad.get();// Two evaluations per dimension for each gradient
+    Ok(res)
+}
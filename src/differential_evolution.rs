@@ -0,0 +1,178 @@
+extern crate nalgebra as na;
+
+use crate::xorwow::Xorwow;
+use crate::nelder_mead;
+use crate::solver_error::SolverError;
+use crate::solver_result::{SolverResult, TerminationReason};
+
+/// Mutation strategy of the differential evolution algorithm. All strategies use a binomial crossover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::enum_variant_names)]// Names follow the usual DE/x/y/z notation
+pub enum DifferentialEvolutionStrategy {
+    Rand1Bin,         // v = x_r1 + F*(x_r2 - x_r3)
+    Best1Bin,         // v = x_best + F*(x_r1 - x_r2)
+    CurrentToBest1Bin,// v = x_i + F*(x_best - x_i) + F*(x_r1 - x_r2)
+}
+
+/// Draws a random index in [0, n) that is not in the excluded list.
+/// @param rng The random number generator.
+/// @param n The number of indices to draw from.
+/// @param excluded The indices that must not be drawn.
+/// @return A random index.
+fn random_index(rng: &mut Xorwow, n: usize, excluded: &[usize]) -> usize {
+    loop {
+        let i: usize = (rng.next_u32() as usize) % n;
+        if !excluded.contains(&i) {
+            return i;
+        }
+    }
+}
+
+/// Computes the standard deviation of the function values of the population.
+/// @param fx The function values of the population.
+/// @return The standard deviation of the function values.
+fn standard_deviation_of_values(fx: &Vec<f64>) -> f64 {
+    let mean: f64 = fx.iter().sum::<f64>() / fx.len() as f64;
+    let mut sum: f64 = 0.0;
+    for value in fx {
+        sum += (value - mean).powi(2);
+    }
+    f64::sqrt(sum / fx.len() as f64)
+}
+
+/// Differential evolution global minimizer.
+/// @param f The function to minimize.
+/// @param n_population Number of individuals in the population (at least 4).
+/// @param lb Lower bounds of the search space.
+/// @param ub Upper bounds of the search space.
+/// @param strategy Mutation strategy.
+/// @param mutation_factor Differential weight F, usually in [0.4, 1.0].
+/// @param crossover_rate Crossover probability CR, in [0, 1].
+/// @param tol Tolerance on the standard deviation of the function values of the population.
+/// @param n_iter_max Maximum number of generations.
+/// @param rng_seed Seed of the random number generator. The optimization is deterministic for a given seed.
+/// @param polish If true, the best individual is refined with nelder_mead_minimize at the end of the optimization, with a tolerance independent of tol.
+/// @return The solution report, holding the best individual and its function value.
+/// @note Trial vectors leaving the bounds [lb, ub] are randomly re-initialised inside the bounds along the offending directions.
+#[allow(clippy::too_many_arguments)]
+pub fn differential_evolution_minimize<F: Fn(&na::DVector<f64>) -> f64>(f: F, n_population: u32, lb: &na::DVector<f64>, ub: &na::DVector<f64>, strategy: DifferentialEvolutionStrategy, mutation_factor: f64, crossover_rate: f64, tol: f64, n_iter_max: u32, rng_seed: u32, polish: bool) -> Result<SolverResult<na::DVector<f64>>, SolverError> {
+    // Parameters
+    let polish_tol: f64 = 1e-12;        // Tolerance of the Nelder-Mead refinement, much tighter than the population spread tol
+    let polish_max_iter_per_dim: u32 = 1000;
+
+    if lb.len() != ub.len() || lb.is_empty() {
+        return Err(SolverError::InvalidInput("lb and ub must have the same non-zero length"));
+    }
+    if lb.iter().zip(ub.iter()).any(|(l, u)| l > u) {
+        return Err(SolverError::InvalidInput("lb must be lower than or equal to ub"));
+    }
+    if n_population < 4 {
+        return Err(SolverError::InvalidInput("the population must contain at least 4 individuals"));
+    }
+    if mutation_factor <= 0.0 || mutation_factor > 2.0 {
+        return Err(SolverError::InvalidInput("the mutation factor must be in (0, 2]"));
+    }
+    if !(0.0..=1.0).contains(&crossover_rate) {
+        return Err(SolverError::InvalidInput("the crossover rate must be in [0, 1]"));
+    }
+
+    let n_pop: usize = n_population as usize;
+    let n_dims: usize = lb.len();
+    let mut rng = Xorwow::new(rng_seed);
+    let mut n_fev: u32 = 0;
+
+    // Initialise the population uniformly in the search space
+    let mut population: Vec<na::DVector<f64>> = Vec::new();
+    let mut fx: Vec<f64> = Vec::new();
+    for _ in 0..n_pop {
+        let x = lb + (ub - lb).component_mul(&rng.rand_vec(n_dims));
+        let f_x = f(&x);
+        n_fev += 1;
+        if f_x.is_nan() {
+            return Err(SolverError::NonFiniteValue);
+        }
+        population.push(x);
+        fx.push(f_x);
+    }
+    let mut i_best: usize = 0;
+    for i in 1..n_pop {
+        if fx[i] < fx[i_best] { i_best = i; }
+    }
+
+    let mut n_iter: u32 = n_iter_max;
+    let mut termination: TerminationReason = TerminationReason::MaxIterations;
+    for iter in 0..n_iter_max {
+        for i in 0..n_pop {
+            // Mutation
+            let mutant: na::DVector<f64> = match strategy {
+                DifferentialEvolutionStrategy::Rand1Bin => {
+                    let r1: usize = random_index(&mut rng, n_pop, &[i]);
+                    let r2: usize = random_index(&mut rng, n_pop, &[i, r1]);
+                    let r3: usize = random_index(&mut rng, n_pop, &[i, r1, r2]);
+                    &population[r1] + mutation_factor*(&population[r2] - &population[r3])
+                },
+                DifferentialEvolutionStrategy::Best1Bin => {
+                    let r1: usize = random_index(&mut rng, n_pop, &[i, i_best]);
+                    let r2: usize = random_index(&mut rng, n_pop, &[i, i_best, r1]);
+                    &population[i_best] + mutation_factor*(&population[r1] - &population[r2])
+                },
+                DifferentialEvolutionStrategy::CurrentToBest1Bin => {
+                    let r1: usize = random_index(&mut rng, n_pop, &[i, i_best]);
+                    let r2: usize = random_index(&mut rng, n_pop, &[i, i_best, r1]);
+                    &population[i] + mutation_factor*(&population[i_best] - &population[i]) + mutation_factor*(&population[r1] - &population[r2])
+                },
+            };
+
+            // Binomial crossover : at least one component (j_rand) is taken from the mutant
+            let j_rand: usize = (rng.next_u32() as usize) % n_dims;
+            let mut trial: na::DVector<f64> = population[i].clone();
+            for j in 0..n_dims {
+                if j == j_rand || rng.next_f64() < crossover_rate {
+                    trial[j] = mutant[j];
+                }
+                if trial[j] < lb[j] || trial[j] > ub[j] {
+                    trial[j] = lb[j] + (ub[j] - lb[j])*rng.next_f64();
+                }
+            }
+
+            // Selection
+            let f_trial: f64 = f(&trial);
+            n_fev += 1;
+            if f_trial.is_nan() {
+                return Err(SolverError::NonFiniteValue);
+            }
+            if f_trial <= fx[i] {
+                population[i] = trial;
+                fx[i] = f_trial;
+                if f_trial < fx[i_best] { i_best = i; }
+            }
+        }
+
+        // Termination condition : convergence of the function values of the population
+        if standard_deviation_of_values(&fx) < tol {
+            n_iter = iter + 1;
+            termination = TerminationReason::FunctionTolerance;
+            break;
+        }
+    }
+
+    let mut x_best: na::DVector<f64> = population[i_best].clone();
+    let mut fx_best: f64 = fx[i_best];
+
+    // Optional local refinement of the best individual, kept only if it stays inside the bounds and improves the objective
+    if polish {
+        let simplex_size: f64 = 1e-2 * (ub - lb).min();
+        if simplex_size > 0.0 {
+            if let Ok(res) = nelder_mead::nelder_mead_minimize(&f, &x_best, simplex_size, polish_tol, polish_max_iter_per_dim*n_dims as u32, false) {
+                n_fev += res.n_fev;
+                let inside: bool = (0..n_dims).all(|j| lb[j] <= res.x[j] && res.x[j] <= ub[j]);
+                if inside && res.f_x < fx_best {
+                    x_best = res.x;
+                    fx_best = res.f_x;
+                }
+            }
+        }
+    }
+
+    Ok(SolverResult::new(x_best, fx_best, n_iter, n_fev, termination))
+}
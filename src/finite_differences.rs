@@ -0,0 +1,79 @@
+extern crate nalgebra as na;
+
+/// Computes the Jacobian matrix of a vector function using forward finite-differences.
+/// @param f vector function
+/// @param x point at which the Jacobian is evaluated
+/// @param f_x value of f at x, which is usually already known by the caller
+/// @param dx_num finite-differences step size
+/// @return Jacobian matrix J[(i, j)] = df_i/dx_j
+/// @note This function evaluates f x.len() times.
+pub fn jacobian_num<F>(f: F, x: &na::DVector<f64>, f_x: &na::DVector<f64>, dx_num: f64) -> na::DMatrix<f64>
+where F : Fn(&na::DVector<f64>) -> na::DVector<f64>
+{
+    let mut jac: na::DMatrix<f64> = na::DMatrix::zeros(f_x.len(), x.len());
+    for j in 0..x.len() {
+        let mut x_dx: na::DVector<f64> = x.clone();
+        x_dx[j] += dx_num;
+        let jac_col = (f(&x_dx) - f_x) / dx_num;
+        jac.set_column(j, &jac_col);
+    }
+    jac
+}
+
+/// Computes the gradient of a scalar function using central finite-differences.
+/// @param f scalar function
+/// @param x point at which the gradient is evaluated
+/// @param dx_num finite-differences step size
+/// @return gradient vector
+/// @note This function evaluates f 2*x.len() times.
+pub fn gradient_num<F>(f: F, x: &na::DVector<f64>, dx_num: f64) -> na::DVector<f64>
+where F : Fn(&na::DVector<f64>) -> f64
+{
+    let mut grad: na::DVector<f64> = na::DVector::zeros(x.len());
+    let mut x_dx: na::DVector<f64> = x.clone();
+    for j in 0..x.len() {
+        x_dx[j] = x[j] + dx_num;
+        let f_p: f64 = f(&x_dx);
+        x_dx[j] = x[j] - dx_num;
+        let f_m: f64 = f(&x_dx);
+        x_dx[j] = x[j];
+        grad[j] = (f_p - f_m)/(2.0*dx_num);
+    }
+    grad
+}
+
+/// Computes the Hessian matrix of a scalar function using central finite-differences.
+/// @param f scalar function
+/// @param x point at which the Hessian is evaluated
+/// @param dx_num finite-differences step size
+/// @return symmetric Hessian matrix H[(i, j)] = d^2f/dx_i dx_j
+/// @note This function evaluates f 2*x.len()^2 + 1 times. Second derivatives are more sensitive to round-off errors than first derivatives, so dx_num should be larger than for the gradient (around the cubic root of the machine epsilon).
+pub fn hessian_num<F>(f: F, x: &na::DVector<f64>, dx_num: f64) -> na::DMatrix<f64>
+where F : Fn(&na::DVector<f64>) -> f64
+{
+    let n: usize = x.len();
+    let mut hess: na::DMatrix<f64> = na::DMatrix::zeros(n, n);
+    let f_x: f64 = f(x);
+    let mut x_dx: na::DVector<f64> = x.clone();
+    for i in 0..n {
+        x_dx[i] = x[i] + dx_num;
+        let f_p: f64 = f(&x_dx);
+        x_dx[i] = x[i] - dx_num;
+        let f_m: f64 = f(&x_dx);
+        x_dx[i] = x[i];
+        hess[(i, i)] = (f_p - 2.0*f_x + f_m)/(dx_num*dx_num);
+        for j in 0..i {
+            let mut f_ij: [f64; 4] = [0.0; 4];
+            for (k, (si, sj)) in [(1.0, 1.0), (1.0, -1.0), (-1.0, 1.0), (-1.0, -1.0)].iter().enumerate() {
+                x_dx[i] = x[i] + si*dx_num;
+                x_dx[j] = x[j] + sj*dx_num;
+                f_ij[k] = f(&x_dx);
+            }
+            x_dx[i] = x[i];
+            x_dx[j] = x[j];
+            hess[(i, j)] = (f_ij[0] - f_ij[1] - f_ij[2] + f_ij[3])/(4.0*dx_num*dx_num);
+            hess[(j, i)] = hess[(i, j)];
+        }
+    }
+    hess
+}
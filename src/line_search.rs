@@ -0,0 +1,344 @@
+extern crate nalgebra as na;
+
+use crate::solver_error::SolverError;
+
+/// Point accepted by a line search along x + alpha*p.
+pub struct LineSearchPoint {
+    pub alpha: f64,              // step length
+    pub x: na::DVector<f64>,     // accepted point x + alpha*p
+    pub f_x: f64,                // function value at the accepted point
+    pub grad: na::DVector<f64>,  // gradient at the accepted point
+}
+
+/// Minimizer of the cubic interpolating phi(a), phi'(a), phi(b) and phi'(b), safeguarded to lie well inside [a, b].
+/// @return the minimizer of the cubic, or the midpoint of [a, b] if the cubic has no suitable minimizer
+fn cubic_interpolation(a: f64, phi_a: f64, dphi_a: f64, b: f64, phi_b: f64, dphi_b: f64) -> f64 {
+    let d1: f64 = dphi_a + dphi_b - 3.0*(phi_a - phi_b)/(a - b);
+    let disc: f64 = d1*d1 - dphi_a*dphi_b;
+    let (lo, hi) = (f64::min(a, b), f64::max(a, b));
+    let margin: f64 = 0.1*(hi - lo);
+    if disc >= 0.0 {
+        let d2: f64 = f64::copysign(f64::sqrt(disc), b - a);
+        let t: f64 = b - (b - a)*(dphi_b + d2 - d1)/(dphi_b - dphi_a + 2.0*d2);
+        if t.is_finite() && t > lo + margin && t < hi - margin {
+            return t;
+        }
+    }
+    0.5*(a + b)
+}
+
+/// Line search satisfying the strong Wolfe conditions (Nocedal & Wright, algorithms 3.5 and 3.6).
+/// @param f function to minimize
+/// @param grad gradient of f
+/// @param x current point
+/// @param f_x function value at x
+/// @param g_x gradient at x
+/// @param p search direction, which must be a descent direction (g_x.dot(p) < 0)
+/// @param alpha0 initial step length
+/// @param c1 sufficient decrease coefficient (usually 1e-4)
+/// @param c2 curvature coefficient (usually 0.9 for quasi-Newton methods, 0.1 for conjugate gradient methods)
+/// @param max_iter maximum number of function evaluations
+/// @param n_fev number of function evaluations, incremented by the line search
+/// @return the accepted point
+#[allow(clippy::too_many_arguments)]
+pub fn strong_wolfe_line_search<F, G>(f: &F, grad: &G, x: &na::DVector<f64>, f_x: f64, g_x: &na::DVector<f64>, p: &na::DVector<f64>, alpha0: f64, c1: f64, c2: f64, max_iter: u32, n_fev: &mut u32) -> Result<LineSearchPoint, SolverError>
+where F : Fn(&na::DVector<f64>) -> f64, G : Fn(&na::DVector<f64>) -> na::DVector<f64>
+{
+    let dphi0: f64 = g_x.dot(p);
+    if dphi0.is_nan() || dphi0 >= 0.0 {
+        return Err(SolverError::InvalidInput("the search direction is not a descent direction"));
+    }
+    let alpha_max: f64 = 1e10;
+
+    // Evaluates phi(alpha) = f(x + alpha*p) and phi'(alpha)
+    let eval = |alpha: f64, n_fev: &mut u32| -> (na::DVector<f64>, f64, na::DVector<f64>, f64) {
+        let x_new: na::DVector<f64> = x + alpha*p;
+        let f_new: f64 = f(&x_new);
+        *n_fev += 1;
+        let g_new: na::DVector<f64> = grad(&x_new);
+        let dphi: f64 = g_new.dot(p);
+        (x_new, f_new, g_new, dphi)
+    };
+
+    // Bracketing phase : increase alpha until an interval containing acceptable points is found
+    let mut alpha_prev: f64 = 0.0;
+    let mut phi_prev: f64 = f_x;
+    let mut dphi_prev: f64 = dphi0;
+    let mut alpha: f64 = alpha0;
+    let mut n_iter: u32 = 0;
+    let (mut lo, mut phi_lo, mut dphi_lo, mut hi, mut phi_hi, mut dphi_hi);
+    loop {
+        let (x_new, f_new, g_new, dphi) = eval(alpha, n_fev);
+        n_iter += 1;
+        if !f_new.is_finite() || !dphi.is_finite() {
+            // Step too long for the function to be defined : shrink it
+            if n_iter >= max_iter { return Err(SolverError::NonFiniteValue); }
+            alpha = 0.5*(alpha_prev + alpha);
+            continue;
+        }
+        if f_new > f_x + c1*alpha*dphi0 || (n_iter > 1 && f_new >= phi_prev) {
+            lo = alpha_prev; phi_lo = phi_prev; dphi_lo = dphi_prev;
+            hi = alpha; phi_hi = f_new; dphi_hi = dphi;
+            break;
+        }
+        if f64::abs(dphi) <= -c2*dphi0 {
+            return Ok(LineSearchPoint { alpha, x: x_new, f_x: f_new, grad: g_new });
+        }
+        if dphi >= 0.0 {
+            lo = alpha; phi_lo = f_new; dphi_lo = dphi;
+            hi = alpha_prev; phi_hi = phi_prev; dphi_hi = dphi_prev;
+            break;
+        }
+        if n_iter >= max_iter || alpha >= alpha_max {
+            return Ok(LineSearchPoint { alpha, x: x_new, f_x: f_new, grad: g_new });// Sufficient decrease holds, only the curvature condition is not met
+        }
+        alpha_prev = alpha; phi_prev = f_new; dphi_prev = dphi;
+        alpha = f64::min(2.0*alpha, alpha_max);
+    }
+
+    // Zoom phase : shrink [lo, hi] until a point satisfying the strong Wolfe conditions is found. lo always satisfies the sufficient decrease condition.
+    let mut best: Option<LineSearchPoint> = None;
+    while n_iter < max_iter {
+        let alpha_j: f64 = cubic_interpolation(lo, phi_lo, dphi_lo, hi, phi_hi, dphi_hi);
+        let (x_new, f_new, g_new, dphi) = eval(alpha_j, n_fev);
+        n_iter += 1;
+        if !f_new.is_finite() || f_new > f_x + c1*alpha_j*dphi0 || f_new >= phi_lo {
+            hi = alpha_j; phi_hi = if f_new.is_finite() { f_new } else { f64::MAX }; dphi_hi = if dphi.is_finite() { dphi } else { 0.0 };
+        } else {
+            if f64::abs(dphi) <= -c2*dphi0 {
+                return Ok(LineSearchPoint { alpha: alpha_j, x: x_new, f_x: f_new, grad: g_new });
+            }
+            if dphi*(hi - lo) >= 0.0 {
+                hi = lo; phi_hi = phi_lo; dphi_hi = dphi_lo;
+            }
+            lo = alpha_j; phi_lo = f_new; dphi_lo = dphi;
+            best = Some(LineSearchPoint { alpha: alpha_j, x: x_new, f_x: f_new, grad: g_new });
+        }
+        if f64::abs(hi - lo) <= f64::EPSILON*f64::max(lo.abs(), 1.0) {
+            break;
+        }
+    }
+
+    // The curvature condition could not be met : fall back to the best point satisfying the sufficient decrease condition
+    if let Some(point) = best {
+        return Ok(point);
+    }
+    if lo > 0.0 {
+        let (x_new, f_new, g_new, _) = eval(lo, n_fev);
+        return Ok(LineSearchPoint { alpha: lo, x: x_new, f_x: f_new, grad: g_new });
+    }
+    Err(SolverError::MaxIterations)
+}
+
+/// Interval of uncertainty of the More-Thuente line search. x is the step with the lowest function value so far, y is the other endpoint.
+struct MoreThuenteInterval {
+    stx: f64, fx: f64, dx: f64,
+    sty: f64, fy: f64, dy: f64,
+    brackt: bool,// true once the interval brackets a minimizer
+}
+
+/// Computes a safeguarded step and updates the interval of uncertainty (dcstep of MINPACK-2).
+/// @param iv interval of uncertainty
+/// @param stp current step
+/// @param fp function value at stp
+/// @param dp derivative at stp
+/// @param stpmin lower bound of the new step
+/// @param stpmax upper bound of the new step
+/// @return new step
+fn more_thuente_step(iv: &mut MoreThuenteInterval, stp: f64, fp: f64, dp: f64, stpmin: f64, stpmax: f64) -> f64 {
+    let (stx, fx, dx) = (iv.stx, iv.fx, iv.dx);
+    let sgnd: f64 = dp*(dx/dx.abs());
+    let stpf: f64;
+    if fp > fx {
+        // Case 1 : higher function value, the minimum is bracketed
+        let theta: f64 = 3.0*(fx - fp)/(stp - stx) + dx + dp;
+        let s: f64 = theta.abs().max(dx.abs()).max(dp.abs());
+        let mut gamma: f64 = s*f64::sqrt((theta/s).powi(2) - (dx/s)*(dp/s));
+        if stp < stx { gamma = -gamma; }
+        let p: f64 = (gamma - dx) + theta;
+        let q: f64 = ((gamma - dx) + gamma) + dp;
+        let stpc: f64 = stx + p/q*(stp - stx);
+        let stpq: f64 = stx + ((dx/((fx - fp)/(stp - stx) + dx))/2.0)*(stp - stx);
+        stpf = if (stpc - stx).abs() < (stpq - stx).abs() { stpc } else { stpc + (stpq - stpc)/2.0 };
+        iv.brackt = true;
+    } else if sgnd < 0.0 {
+        // Case 2 : lower function value and derivatives of opposite sign, the minimum is bracketed
+        let theta: f64 = 3.0*(fx - fp)/(stp - stx) + dx + dp;
+        let s: f64 = theta.abs().max(dx.abs()).max(dp.abs());
+        let mut gamma: f64 = s*f64::sqrt((theta/s).powi(2) - (dx/s)*(dp/s));
+        if stp > stx { gamma = -gamma; }
+        let p: f64 = (gamma - dp) + theta;
+        let q: f64 = ((gamma - dp) + gamma) + dx;
+        let stpc: f64 = stp + p/q*(stx - stp);
+        let stpq: f64 = stp + (dp/(dp - dx))*(stx - stp);
+        stpf = if (stpc - stp).abs() > (stpq - stp).abs() { stpc } else { stpq };
+        iv.brackt = true;
+    } else if dp.abs() < dx.abs() {
+        // Case 3 : lower function value, derivatives of the same sign, and the magnitude of the derivative decreases
+        let theta: f64 = 3.0*(fx - fp)/(stp - stx) + dx + dp;
+        let s: f64 = theta.abs().max(dx.abs()).max(dp.abs());
+        let mut gamma: f64 = s*f64::sqrt(f64::max(0.0, (theta/s).powi(2) - (dx/s)*(dp/s)));
+        if stp > stx { gamma = -gamma; }
+        let p: f64 = (gamma - dp) + theta;
+        let q: f64 = (gamma + (dx - dp)) + gamma;
+        let r: f64 = p/q;
+        let stpc: f64 = if r < 0.0 && gamma != 0.0 { stp + r*(stx - stp) } else if stp > stx { stpmax } else { stpmin };
+        let stpq: f64 = stp + (dp/(dp - dx))*(stx - stp);
+        if iv.brackt {
+            let stp_best: f64 = if (stpc - stp).abs() < (stpq - stp).abs() { stpc } else { stpq };
+            stpf = if stp > stx { f64::min(stp + 0.66*(iv.sty - stp), stp_best) } else { f64::max(stp + 0.66*(iv.sty - stp), stp_best) };
+        } else {
+            let stp_best: f64 = if (stpc - stp).abs() > (stpq - stp).abs() { stpc } else { stpq };
+            stpf = stp_best.clamp(stpmin, stpmax);
+        }
+    } else {
+        // Case 4 : lower function value, derivatives of the same sign, and the magnitude of the derivative does not decrease
+        if iv.brackt {
+            let (sty, fy, dy) = (iv.sty, iv.fy, iv.dy);
+            let theta: f64 = 3.0*(fp - fy)/(sty - stp) + dy + dp;
+            let s: f64 = theta.abs().max(dy.abs()).max(dp.abs());
+            let mut gamma: f64 = s*f64::sqrt((theta/s).powi(2) - (dy/s)*(dp/s));
+            if stp > sty { gamma = -gamma; }
+            let p: f64 = (gamma - dp) + theta;
+            let q: f64 = ((gamma - dp) + gamma) + dy;
+            stpf = stp + p/q*(sty - stp);
+        } else if stp > stx {
+            stpf = stpmax;
+        } else {
+            stpf = stpmin;
+        }
+    }
+
+    // Update the interval of uncertainty
+    if fp > fx {
+        iv.sty = stp; iv.fy = fp; iv.dy = dp;
+    } else {
+        if sgnd < 0.0 {
+            iv.sty = stx; iv.fy = fx; iv.dy = dx;
+        }
+        iv.stx = stp; iv.fx = fp; iv.dx = dp;
+    }
+    stpf
+}
+
+/// Line search of More and Thuente, satisfying the strong Wolfe conditions (dcsrch of MINPACK-2).
+/// @param f function to minimize
+/// @param grad gradient of f
+/// @param x current point
+/// @param f_x function value at x
+/// @param g_x gradient at x
+/// @param p search direction, which must be a descent direction (g_x.dot(p) < 0)
+/// @param alpha0 initial step length
+/// @param alpha_max largest step length allowed (for instance the distance to the bounds along p)
+/// @param c1 sufficient decrease coefficient (usually 1e-4)
+/// @param c2 curvature coefficient (usually 0.9 for quasi-Newton methods, 0.1 for conjugate gradient methods)
+/// @param max_iter maximum number of function evaluations
+/// @param n_fev number of function evaluations, incremented by the line search
+/// @return the accepted point
+/// @note The step is safeguarded with cubic and quadratic interpolations, and the method is guaranteed to terminate in a finite number of steps.
+#[allow(clippy::too_many_arguments)]
+pub fn more_thuente_line_search<F, G>(f: &F, grad: &G, x: &na::DVector<f64>, f_x: f64, g_x: &na::DVector<f64>, p: &na::DVector<f64>, alpha0: f64, alpha_max: f64, c1: f64, c2: f64, max_iter: u32, n_fev: &mut u32) -> Result<LineSearchPoint, SolverError>
+where F : Fn(&na::DVector<f64>) -> f64, G : Fn(&na::DVector<f64>) -> na::DVector<f64>
+{
+    // Parameters
+    let xtol: f64 = 1e-10;  // Relative tolerance on the width of the interval of uncertainty
+    let stpmin: f64 = 0.0;  // Smallest step allowed
+    let stpmax: f64 = f64::min(alpha_max, 1e10);// Largest step allowed
+    let xtrapl: f64 = 1.1;  // Lower and upper extrapolation factors of the step before the minimizer is bracketed
+    let xtrapu: f64 = 4.0;
+
+    let ginit: f64 = g_x.dot(p);
+    if ginit.is_nan() || ginit >= 0.0 {
+        return Err(SolverError::InvalidInput("the search direction is not a descent direction"));
+    }
+    let gtest: f64 = c1*ginit;
+    let mut stage: u32 = 1;
+    let mut width: f64 = stpmax - stpmin;
+    let mut width1: f64 = 2.0*width;
+    let mut iv = MoreThuenteInterval { stx: 0.0, fx: f_x, dx: ginit, sty: 0.0, fy: f_x, dy: ginit, brackt: false };
+    let mut stmin: f64 = 0.0;
+    let mut stmax: f64 = alpha0 + xtrapu*alpha0;
+    let mut stp: f64 = alpha0.clamp(stpmin, stpmax);
+    let mut best: Option<LineSearchPoint> = None;// Best point satisfying the sufficient decrease condition
+
+    for _iter in 0..max_iter {
+        let x_new: na::DVector<f64> = x + stp*p;
+        let mut f_new: f64 = f(&x_new);
+        *n_fev += 1;
+        let g_new: na::DVector<f64> = grad(&x_new);
+        let mut g: f64 = g_new.dot(p);
+        if !f_new.is_finite() || !g.is_finite() {
+            // Treat undefined values as a very high function value, which brackets the minimizer
+            f_new = f64::MAX.sqrt();
+            g = 0.0;
+        }
+
+        let ftest: f64 = f_x + stp*gtest;
+        if stage == 1 && f_new <= ftest && g >= 0.0 {
+            stage = 2;
+        }
+
+        // Test for convergence
+        if f_new <= ftest && g.abs() <= -c2*ginit {
+            return Ok(LineSearchPoint { alpha: stp, x: x_new, f_x: f_new, grad: g_new });
+        }
+        let improves: bool = match &best { Some(b) => f_new < b.f_x, None => true };
+        if f_new <= ftest && improves {
+            best = Some(LineSearchPoint { alpha: stp, x: x_new, f_x: f_new, grad: g_new });
+        }
+
+        // Test for warnings : rounding errors, interval too small, or step at its bounds
+        if (iv.brackt && (stp <= stmin || stp >= stmax || stmax - stmin <= xtol*stmax))
+            || (stp == stpmax && f_new <= ftest && g <= gtest)
+            || (stp == stpmin && (f_new > ftest || g >= gtest)) {
+            break;
+        }
+
+        // In the first stage, use a modified function to compute the step if a lower function value has been obtained but the decrease is not sufficient
+        if stage == 1 && f_new <= iv.fx && f_new > ftest {
+            let mut iv_mod = MoreThuenteInterval {
+                stx: iv.stx, fx: iv.fx - iv.stx*gtest, dx: iv.dx - gtest,
+                sty: iv.sty, fy: iv.fy - iv.sty*gtest, dy: iv.dy - gtest,
+                brackt: iv.brackt,
+            };
+            stp = more_thuente_step(&mut iv_mod, stp, f_new - stp*gtest, g - gtest, stmin, stmax);
+            iv = MoreThuenteInterval {
+                stx: iv_mod.stx, fx: iv_mod.fx + iv_mod.stx*gtest, dx: iv_mod.dx + gtest,
+                sty: iv_mod.sty, fy: iv_mod.fy + iv_mod.sty*gtest, dy: iv_mod.dy + gtest,
+                brackt: iv_mod.brackt,
+            };
+        } else {
+            stp = more_thuente_step(&mut iv, stp, f_new, g, stmin, stmax);
+        }
+
+        // Decide if a bisection step is needed
+        if iv.brackt {
+            if (iv.sty - iv.stx).abs() >= 0.66*width1 {
+                stp = iv.stx + 0.5*(iv.sty - iv.stx);
+            }
+            width1 = width;
+            width = (iv.sty - iv.stx).abs();
+        }
+
+        // Set the minimum and maximum steps allowed
+        if iv.brackt {
+            stmin = f64::min(iv.stx, iv.sty);
+            stmax = f64::max(iv.stx, iv.sty);
+        } else {
+            stmin = stp + xtrapl*(stp - iv.stx);
+            stmax = stp + xtrapu*(stp - iv.stx);
+        }
+        stp = stp.clamp(stpmin, stpmax);
+
+        // If further progress is not possible, let stp be the best point obtained so far
+        if iv.brackt && (stp <= stmin || stp >= stmax || stmax - stmin <= xtol*stmax) {
+            stp = iv.stx;
+        }
+    }
+
+    // The strong Wolfe conditions could not be met : fall back to the best point satisfying the sufficient decrease condition
+    match best {
+        Some(point) => Ok(point),
+        None => Err(SolverError::MaxIterations),
+    }
+}
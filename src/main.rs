@@ -1,18 +1,84 @@
 extern crate colored;
 extern crate nalgebra as na;
 
+mod solver_error;
+mod solver_result;
+mod finite_differences;
 mod univariate_solvers;
 mod univariate_minimizers;
 mod nelder_mead;
+mod xorwow;
 mod particle_swarm_optimization;
+mod differential_evolution;
 mod non_linear_least_squares;
+mod multivariate_solvers;
+mod line_search;
+mod quasi_newton;
+mod conjugate_gradient;
+mod trust_region;
+mod polynomial;
+mod complex_solvers;
 
 use colored::Colorize;
+use na::ComplexField;
 
 fn rosenbrock(x: &na::DVector<f64>) -> f64 {
     return (1.0-x[0]).powi(2) + 100.0*(x[1] - x[0].powi(2)).powi(2);
 }
 
+/// Rosenbrock's function written as a system of equations F(x) = 0, whose solution is (1, 1)
+fn rosenbrock_system(x: &na::DVector<f64>) -> na::DVector<f64> {
+    na::DVector::from_vec(vec![10.0*(x[1] - x[0].powi(2)), 1.0 - x[0]])
+}
+
+fn jac_rosenbrock_system(x: &na::DVector<f64>) -> na::DMatrix<f64> {
+    na::DMatrix::from_row_slice(2, 2, &[-20.0*x[0], 10.0, -1.0, 0.0])
+}
+
+/// Intersection of a circle and a hyperbola, whose solution closest to (3, 0.5) is (2, 1)
+fn circle_hyperbola_system(x: &na::DVector<f64>) -> na::DVector<f64> {
+    na::DVector::from_vec(vec![x[0].powi(2) + x[1].powi(2) - 5.0, x[0]*x[1] - 2.0])
+}
+
+fn jac_circle_hyperbola_system(x: &na::DVector<f64>) -> na::DMatrix<f64> {
+    na::DMatrix::from_row_slice(2, 2, &[2.0*x[0], 2.0*x[1], x[1], x[0]])
+}
+
+/// Powell's badly scaled function, whose solution is (1.098159329699817e-5, 9.106146739866524)
+fn powell_badly_scaled_system(x: &na::DVector<f64>) -> na::DVector<f64> {
+    na::DVector::from_vec(vec![1e4*x[0]*x[1] - 1.0, f64::exp(-x[0]) + f64::exp(-x[1]) - 1.0001])
+}
+
+fn jac_powell_badly_scaled_system(x: &na::DVector<f64>) -> na::DMatrix<f64> {
+    na::DMatrix::from_row_slice(2, 2, &[1e4*x[1], 1e4*x[0], -f64::exp(-x[0]), -f64::exp(-x[1])])
+}
+
+fn grad_rosenbrock(x: &na::DVector<f64>) -> na::DVector<f64> {
+    na::DVector::from_vec(vec![-2.0*(1.0 - x[0]) - 400.0*x[0]*(x[1] - x[0].powi(2)), 200.0*(x[1] - x[0].powi(2))])
+}
+
+fn hess_rosenbrock(x: &na::DVector<f64>) -> na::DMatrix<f64> {
+    na::DMatrix::from_row_slice(2, 2, &[2.0 - 400.0*(x[1] - 3.0*x[0].powi(2)), -400.0*x[0], -400.0*x[0], 200.0])
+}
+
+/// Extended Rosenbrock function : sum of independent Rosenbrock functions over the pairs (x[2i], x[2i+1]), whose minimum is (1, ..., 1)
+fn rosenbrock_extended(x: &na::DVector<f64>) -> f64 {
+    let mut f_x: f64 = 0.0;
+    for i in (0..x.len()-1).step_by(2) {
+        f_x += (1.0-x[i]).powi(2) + 100.0*(x[i+1] - x[i].powi(2)).powi(2);
+    }
+    f_x
+}
+
+fn grad_rosenbrock_extended(x: &na::DVector<f64>) -> na::DVector<f64> {
+    let mut g: na::DVector<f64> = na::DVector::zeros(x.len());
+    for i in (0..x.len()-1).step_by(2) {
+        g[i] = -2.0*(1.0 - x[i]) - 400.0*x[i]*(x[i+1] - x[i].powi(2));
+        g[i+1] = 200.0*(x[i+1] - x[i].powi(2));
+    }
+    g
+}
+
 ///  x   sin(x)
 /// e  + ──────
 ///        x
@@ -42,19 +108,32 @@ fn ddfct(x : f64) -> f64 {
     }
 }
 
-fn check_result(x : f64, x_true : f64, tol : f64, test_name: &str, verbose: bool) -> u32 {
+/// Wallis' cubic x^3 - 2x - 5, used to test polynomial root finders
+fn poly_wallis(x : f64) -> f64 {
+    x.powi(3) - 2.0*x - 5.0
+}
+
+fn dpoly_wallis(x : f64) -> f64 {
+    3.0*x.powi(2) - 2.0
+}
+
+fn ddpoly_wallis(x : f64) -> f64 {
+    6.0*x
+}
+
+fn check_result(x : f64, f_x : f64, x_true : f64, tol : f64, test_name: &str, verbose: bool) -> u32 {
     let diff : f64 = (x - x_true).abs();
     let test_name_padded: String = format!("{:<30}", test_name);
     if diff < tol {
         if verbose {
-            println!("{}\t: x = {}\tf(x) = {}\t{}", test_name_padded, format!("{:<20}", x), format!("{:<40}", fct(x)), "passed".green());
+            println!("{}\t: x = {:<20}\tf(x) = {:<40}\t{}", test_name_padded, x, f_x, "passed".green());
         } else {
             println!("{} {}", test_name_padded, "passed".green());
         }
         return 1;
     } else {
         if verbose {
-            println!("{}\t: x = {}\tf(x) = {}\t{} (expected {}, delta = {})", test_name_padded, format!("{:<20}", x), format!("{:<40}", fct(x)), "failed".red(), x_true, (x - x_true));
+            println!("{}\t: x = {:<20}\tf(x) = {:<40}\t{} (expected {}, delta = {})", test_name_padded, x, f_x, "failed".red(), x_true, (x - x_true));
         } else {
             println!("{} {} : expected {}, got {}", test_name_padded, "failed".red(), x_true, x);
         }
@@ -103,6 +182,89 @@ fn check_result_vector(x : &na::DVector<f64>, x_true : &na::DVector<f64>, tol :
     }
 }
 
+fn check_improvement(f_x: f64, f_x_ref: f64, test_name: &str, verbose: bool) -> u32 {
+    let test_name_padded: String = format!("{:<30}", test_name);
+    if f_x < f_x_ref {
+        if verbose {
+            println!("{}\t: f(x) = {}\tf(x_ref) = {}\t{}", test_name_padded, f_x, f_x_ref, "passed".green());
+        } else {
+            println!("{} {}", test_name_padded, "passed".green());
+        }
+        1
+    } else {
+        println!("{} {} : expected f(x) < {}, got {}", test_name_padded, "failed".red(), f_x_ref, f_x);
+        0
+    }
+}
+
+fn check_termination(termination: solver_result::TerminationReason, converged: bool, termination_true: solver_result::TerminationReason, test_name: &str, verbose: bool) -> u32 {
+    let converged_true: bool = termination_true.is_converged();
+    let test_name_padded: String = format!("{:<30}", test_name);
+    if termination == termination_true && converged == converged_true {
+        if verbose {
+            println!("{}	: termination = {:?}	converged = {}	{}", test_name_padded, termination, converged, "passed".green());
+        } else {
+            println!("{} {}", test_name_padded, "passed".green());
+        }
+        1
+    } else {
+        println!("{} {} : expected {:?} (converged = {}), got {:?} (converged = {})", test_name_padded, "failed".red(), termination_true, converged_true, termination, converged);
+        0
+    }
+}
+
+fn check_error<T>(res: Result<T, solver_error::SolverError>, err_true: solver_error::SolverError, test_name: &str, verbose: bool) -> u32 {
+    let test_name_padded: String = format!("{:<30}", test_name);
+    match res {
+        Err(err) if err == err_true => {
+            if verbose {
+                println!("{}\t: error = {}\t{}", test_name_padded, err, "passed".green());
+            } else {
+                println!("{} {}", test_name_padded, "passed".green());
+            }
+            1
+        },
+        Err(err) => {
+            println!("{} {} : expected error {:?}, got {:?}", test_name_padded, "failed".red(), err_true, err);
+            0
+        },
+        Ok(_) => {
+            println!("{} {} : expected error {:?}, got a solution", test_name_padded, "failed".red(), err_true);
+            0
+        },
+    }
+}
+
+fn check_root_bracket(bracket: &univariate_solvers::RootBracket, test_name: &str, verbose: bool) -> u32 {
+    let test_name_padded: String = format!("{:<30}", test_name);
+    if bracket.f_a*bracket.f_b <= 0.0 {
+        if verbose {
+            println!("{}\t: a = {}\tb = {}\t{}", test_name_padded, bracket.a, bracket.b, "passed".green());
+        } else {
+            println!("{} {}", test_name_padded, "passed".green());
+        }
+        1
+    } else {
+        println!("{} {} : f(a) = {}, f(b) = {} at a = {}, b = {}", test_name_padded, "failed".red(), bracket.f_a, bracket.f_b, bracket.a, bracket.b);
+        0
+    }
+}
+
+fn check_minimum_bracket(bracket: &univariate_minimizers::MinimumBracket, test_name: &str, verbose: bool) -> u32 {
+    let test_name_padded: String = format!("{:<30}", test_name);
+    if (bracket.b - bracket.a)*(bracket.c - bracket.b) > 0.0 && bracket.f_b <= bracket.f_a && bracket.f_b <= bracket.f_c {
+        if verbose {
+            println!("{}\t: a = {}\tb = {}\tc = {}\t{}", test_name_padded, bracket.a, bracket.b, bracket.c, "passed".green());
+        } else {
+            println!("{} {}", test_name_padded, "passed".green());
+        }
+        1
+    } else {
+        println!("{} {} : f(a) = {}, f(b) = {}, f(c) = {} at a = {}, b = {}, c = {}", test_name_padded, "failed".red(), bracket.f_a, bracket.f_b, bracket.f_c, bracket.a, bracket.b, bracket.c);
+        0
+    }
+}
+
 fn print_test_results(num_tests_passed: u32, num_tests_total: u32) {
     let ratio_str:String = format!("{}/{} ({} %)", num_tests_passed, num_tests_total, ((num_tests_passed as f64)/(num_tests_total as f64)*100.0).round());
     if num_tests_passed == num_tests_total {
@@ -119,25 +281,104 @@ fn test_univariate_solvers(verbose: bool) {
     let max_iter : u32 = 100;
     let dx_num :   f64 = 1e-6;
     let mut num_tests_passed : u32 = 0;
-    let num_tests_total :      u32 = 7;
+    let num_tests_total :      u32 = 42;
 
     let x_mathematica: f64   = -3.26650043678562449167148755288;// 30 digits of precision
     let x_mathematica_2: f64 = -6.27133405258685307845641527902;// 30 digits of precision
-    // let x_mathematica_3: f64 = -9.42553801930504142668603949182;// 30 digits of precision
-    let x_newton:     f64 = univariate_solvers::newton_solve(&(fct as fn(f64) -> f64), &(dfct as fn(f64) -> f64), x0, tol, max_iter);
-    let x_newton_num: f64 = univariate_solvers::newton_solve_num(&(fct as fn(f64) -> f64), x0, tol, dx_num, max_iter);
-    let x_halley:     f64 = univariate_solvers::halley_solve(&(fct as fn(f64) -> f64), &(dfct as fn(f64) -> f64), &(ddfct as fn(f64) -> f64), x0, tol, max_iter, false).unwrap();
-    let x_halley_num: f64 = univariate_solvers::halley_solve_num(&(fct as fn(f64) -> f64), x0, tol, dx_num, max_iter, false).unwrap();
-    let x_bisection : f64 = univariate_solvers::bisection_solve(&(fct as fn(f64) -> f64), -5.0, 1.0, tol).unwrap();
-    let x_secant :    f64 = univariate_solvers::secant_solve(&(fct as fn(f64) -> f64), -1.0, 1.0, tol, max_iter);
-    let x_ridder :    f64 = univariate_solvers::ridder_solve(&(fct as fn(f64) -> f64), -5.0, 1.0, tol, max_iter).unwrap();
-    num_tests_passed += check_result(x_newton, x_mathematica, tol, "Newton's method", verbose);
-    num_tests_passed += check_result(x_newton_num, x_mathematica, tol, "Newton's method (num)", verbose);
-    num_tests_passed += check_result(x_halley, x_mathematica_2, tol, "Halley's method", verbose);
-    num_tests_passed += check_result(x_halley_num, x_mathematica_2, tol, "Halley's method (num)", verbose);
-    num_tests_passed += check_result(x_bisection, x_mathematica, tol, "Bisection method", verbose);
-    num_tests_passed += check_result(x_secant, x_mathematica, tol, "Secant method", verbose);
-    num_tests_passed += check_result(x_ridder, x_mathematica, tol, "Ridder's method", verbose);
+    let x_mathematica_3: f64 = -9.42553801930504;// Rounded to double precision
+    let x_wallis: f64        = 2.0945514815423265;// Rounded to double precision
+    let sol_newton = univariate_solvers::newton_solve(fct, dfct, x0, tol, max_iter).unwrap();
+    let sol_newton_num = univariate_solvers::newton_solve_num(fct, x0, tol, dx_num, max_iter).unwrap();
+    let sol_halley = univariate_solvers::halley_solve(fct, dfct, ddfct, x0, tol, max_iter, false).unwrap();
+    let sol_halley_num = univariate_solvers::halley_solve_num(fct, x0, tol, dx_num, max_iter, false).unwrap();
+    let sol_householder_1 = univariate_solvers::householder_solve(|x: f64| vec![fct(x), dfct(x)], 1, x0, tol, max_iter).unwrap();
+    let sol_householder_2 = univariate_solvers::householder_solve(|x: f64| vec![fct(x), dfct(x), ddfct(x)], 2, x0, tol, max_iter).unwrap();
+    let sol_householder_3 = univariate_solvers::householder_solve(|x: f64| vec![poly_wallis(x), dpoly_wallis(x), ddpoly_wallis(x), 6.0], 3, x0, tol, max_iter).unwrap();
+    let sol_steffensen = univariate_solvers::steffensen_solve(fct, x0, tol, max_iter).unwrap();
+    let sol_steffensen_poly = univariate_solvers::steffensen_solve(poly_wallis, 2.0, tol, max_iter).unwrap();// Does not converge from x0
+    let sol_laguerre = univariate_solvers::laguerre_solve(fct, dfct, ddfct, x0, None, tol, max_iter, false).unwrap();
+    let sol_laguerre_num = univariate_solvers::laguerre_solve_num(fct, x0, None, tol, dx_num, max_iter, false).unwrap();
+    let sol_laguerre_poly = univariate_solvers::laguerre_solve(poly_wallis, dpoly_wallis, ddpoly_wallis, x0, Some(3), tol, max_iter, false).unwrap();
+    let sol_laguerre_poly_num = univariate_solvers::laguerre_solve_num(poly_wallis, x0, Some(3), tol, dx_num, max_iter, false).unwrap();
+    let sol_bisection = univariate_solvers::bisection_solve(fct, -5.0, 1.0, tol).unwrap();
+    let sol_secant = univariate_solvers::secant_solve(fct, -1.0, 1.0, tol, max_iter).unwrap();
+    let sol_ridder = univariate_solvers::ridder_solve(fct, -5.0, 1.0, tol, max_iter).unwrap();
+    let sol_brent = univariate_solvers::brent_solve(fct, -5.0, 1.0, tol, max_iter).unwrap();
+    let sol_itp = univariate_solvers::itp_solve(fct, -5.0, 1.0, tol, max_iter).unwrap();
+    let sol_toms748 = univariate_solvers::toms748_solve(fct, -5.0, 1.0, tol, max_iter).unwrap();
+    let sol_illinois = univariate_solvers::regula_falsi_solve(fct, -5.0, 1.0, univariate_solvers::RegulaFalsiVariant::Illinois, tol, max_iter).unwrap();
+    let sol_pegasus = univariate_solvers::regula_falsi_solve(fct, -5.0, 1.0, univariate_solvers::RegulaFalsiVariant::Pegasus, tol, max_iter).unwrap();
+    let sol_anderson_bjorck = univariate_solvers::regula_falsi_solve(fct, -5.0, 1.0, univariate_solvers::RegulaFalsiVariant::AndersonBjorck, tol, max_iter).unwrap();
+    num_tests_passed += check_result(sol_newton.x, sol_newton.f_x, x_mathematica, tol, "Newton's method", verbose);
+    num_tests_passed += check_result(sol_newton_num.x, sol_newton_num.f_x, x_mathematica, tol, "Newton's method (num)", verbose);
+    num_tests_passed += check_result(sol_halley.x, sol_halley.f_x, x_mathematica_2, tol, "Halley's method", verbose);
+    num_tests_passed += check_result(sol_halley_num.x, sol_halley_num.f_x, x_mathematica_2, tol, "Halley's method (num)", verbose);
+    num_tests_passed += check_result(sol_householder_1.x, sol_householder_1.f_x, x_mathematica, tol, "Householder's method (d = 1)", verbose);
+    num_tests_passed += check_result(sol_householder_2.x, sol_householder_2.f_x, x_mathematica_2, tol, "Householder's method (d = 2)", verbose);
+    num_tests_passed += check_result(sol_householder_3.x, sol_householder_3.f_x, x_wallis, tol, "Householder's method (d = 3, poly)", verbose);
+    num_tests_passed += check_result(sol_steffensen.x, sol_steffensen.f_x, x_mathematica, tol, "Steffensen's method", verbose);
+    num_tests_passed += check_result(sol_steffensen_poly.x, sol_steffensen_poly.f_x, x_wallis, tol, "Steffensen's method (poly)", verbose);
+    num_tests_passed += check_result(sol_laguerre.x, sol_laguerre.f_x, x_mathematica, tol, "Laguerre's method", verbose);
+    num_tests_passed += check_result(sol_laguerre_num.x, sol_laguerre_num.f_x, x_mathematica, tol, "Laguerre's method (num)", verbose);
+    num_tests_passed += check_result(sol_laguerre_poly.x, sol_laguerre_poly.f_x, x_wallis, tol, "Laguerre's method (poly)", verbose);
+    num_tests_passed += check_result(sol_laguerre_poly_num.x, sol_laguerre_poly_num.f_x, x_wallis, tol, "Laguerre's method (poly, num)", verbose);
+    num_tests_passed += check_result(sol_bisection.x, sol_bisection.f_x, x_mathematica, tol, "Bisection method", verbose);
+    num_tests_passed += check_result(sol_secant.x, sol_secant.f_x, x_mathematica, tol, "Secant method", verbose);
+    num_tests_passed += check_result(sol_ridder.x, sol_ridder.f_x, x_mathematica, tol, "Ridder's method", verbose);
+    num_tests_passed += check_result(sol_brent.x, sol_brent.f_x, x_mathematica, tol, "Brent's method", verbose);
+    num_tests_passed += check_result(sol_itp.x, sol_itp.f_x, x_mathematica, tol, "ITP method", verbose);
+    num_tests_passed += check_result(sol_toms748.x, sol_toms748.f_x, x_mathematica, tol, "TOMS 748 method", verbose);
+    num_tests_passed += check_result(sol_illinois.x, sol_illinois.f_x, x_mathematica, tol, "Regula falsi (Illinois)", verbose);
+    num_tests_passed += check_result(sol_pegasus.x, sol_pegasus.f_x, x_mathematica, tol, "Regula falsi (Pegasus)", verbose);
+    num_tests_passed += check_result(sol_anderson_bjorck.x, sol_anderson_bjorck.f_x, x_mathematica, tol, "Regula falsi (Anderson-Bjorck)", verbose);
+
+    let err_secant = univariate_solvers::secant_solve(fct, -1.0, 1.0, tol, 2);
+    let err_ridder = univariate_solvers::ridder_solve(fct, -1.0, 1.0, tol, max_iter);
+    num_tests_passed += check_termination(sol_newton.termination, sol_newton.converged, solver_result::TerminationReason::StepTolerance, "Newton's method (report)", verbose);
+    num_tests_passed += check_error(err_secant, solver_error::SolverError::MaxIterations, "Secant method (max iter)", verbose);
+    num_tests_passed += check_error(err_ridder, solver_error::SolverError::NotBracketed, "Ridder's method (not bracketed)", verbose);
+    num_tests_passed += check_error(univariate_solvers::itp_solve(fct, -1.0, 1.0, tol, max_iter), solver_error::SolverError::NotBracketed, "ITP method (not bracketed)", verbose);
+    num_tests_passed += check_error(univariate_solvers::toms748_solve(fct, -1.0, 1.0, tol, max_iter), solver_error::SolverError::NotBracketed, "TOMS 748 method (not bracketed)", verbose);
+    num_tests_passed += check_error(univariate_solvers::regula_falsi_solve(fct, -1.0, 1.0, univariate_solvers::RegulaFalsiVariant::Illinois, tol, max_iter), solver_error::SolverError::NotBracketed, "Regula falsi (not bracketed)", verbose);
+
+    // Without bracket : the interval is expanded from x0 until it brackets a root
+    let root_bracket: univariate_solvers::RootBracket = univariate_solvers::bracket_root(fct, x0, None).unwrap();
+    num_tests_passed += check_root_bracket(&root_bracket, "Root bracketing", verbose);
+    let sol_bisection_auto = univariate_solvers::bisection_solve_auto(fct, x0, None, tol).unwrap();
+    let sol_ridder_auto = univariate_solvers::ridder_solve_auto(fct, x0, None, tol, max_iter).unwrap();
+    let sol_brent_auto = univariate_solvers::brent_solve_auto(fct, x0, None, tol, max_iter).unwrap();
+    num_tests_passed += check_result(sol_bisection_auto.x, sol_bisection_auto.f_x, x_mathematica, tol, "Bisection method (auto)", verbose);
+    num_tests_passed += check_result(sol_ridder_auto.x, sol_ridder_auto.f_x, x_mathematica, tol, "Ridder's method (auto)", verbose);
+    num_tests_passed += check_result(sol_brent_auto.x, sol_brent_auto.f_x, x_mathematica, tol, "Brent's method (auto)", verbose);
+
+    // Subdivision of a range containing three roots
+    let brackets: Vec<univariate_solvers::RootBracket> = univariate_solvers::subdivide_root_brackets(fct, -10.0, 1.0, 100).unwrap();
+    let x_subdivision: na::DVector<f64> = na::DVector::from_iterator(brackets.len(), brackets.iter().map(|br| univariate_solvers::ridder_solve(fct, br.a, br.b, tol, max_iter).unwrap().x));
+    num_tests_passed += check_result_vector(&x_subdivision, &na::DVector::from_vec(vec![x_mathematica_3, x_mathematica_2, x_mathematica]), tol, "Root bracket subdivision", verbose);
+    num_tests_passed += check_error(univariate_solvers::bracket_root(|x: f64| x*x + 1.0, x0, None), solver_error::SolverError::NotBracketed, "Root bracketing (no root)", verbose);
+
+    // All the roots over an interval, including a double root of (x - 1)^2*(x + 2)
+    let roots: Vec<univariate_solvers::RootEstimate> = univariate_solvers::all_roots(fct, -10.0, 1.0, 50, tol, max_iter).unwrap();
+    let x_roots: na::DVector<f64> = na::DVector::from_iterator(roots.len(), roots.iter().map(|r| r.x));
+    let multiplicities: na::DVector<f64> = na::DVector::from_iterator(roots.len(), roots.iter().map(|r| r.multiplicity as f64));
+    num_tests_passed += check_result_vector(&x_roots, &na::DVector::from_vec(vec![x_mathematica_3, x_mathematica_2, x_mathematica]), tol, "All roots", verbose);
+    num_tests_passed += check_result_vector(&multiplicities, &na::DVector::from_vec(vec![1.0, 1.0, 1.0]), 0.5, "All roots (multiplicity)", verbose);
+    let roots_double: Vec<univariate_solvers::RootEstimate> = univariate_solvers::all_roots(|x: f64| (x - 1.0).powi(2)*(x + 2.0), -3.0, 3.0, 50, tol, max_iter).unwrap();
+    let x_roots_double: na::DVector<f64> = na::DVector::from_iterator(roots_double.len(), roots_double.iter().map(|r| r.x));
+    let multiplicities_double: na::DVector<f64> = na::DVector::from_iterator(roots_double.len(), roots_double.iter().map(|r| r.multiplicity as f64));
+    num_tests_passed += check_result_vector(&x_roots_double, &na::DVector::from_vec(vec![-2.0, 1.0]), 1e-6, "All roots (double root)", verbose);
+    num_tests_passed += check_result_vector(&multiplicities_double, &na::DVector::from_vec(vec![1.0, 2.0]), 0.5, "All roots (double, multiplicity)", verbose);
+    let roots_on_samples: Vec<univariate_solvers::RootEstimate> = univariate_solvers::all_roots(|x: f64| (x - 1.0).powi(2)*(x + 2.0), -3.0, 3.0, 6, tol, max_iter).unwrap();// Both roots are samples
+    let multiplicities_on_samples: na::DVector<f64> = na::DVector::from_iterator(roots_on_samples.len(), roots_on_samples.iter().map(|r| r.multiplicity as f64));
+    num_tests_passed += check_result_vector(&multiplicities_on_samples, &na::DVector::from_vec(vec![1.0, 2.0]), 0.5, "All roots (exact, multiplicity)", verbose);
+    let roots_fallback: Vec<univariate_solvers::RootEstimate> = univariate_solvers::all_roots(fct, -10.0, 1.0, 50, tol, 1).unwrap();// Ridder's method fails with a single iteration
+    let x_roots_fallback: na::DVector<f64> = na::DVector::from_iterator(roots_fallback.len(), roots_fallback.iter().map(|r| r.x));
+    num_tests_passed += check_result_vector(&x_roots_fallback, &na::DVector::from_vec(vec![x_mathematica_3, x_mathematica_2, x_mathematica]), tol, "All roots (refinement failure)", verbose);
+    let roots_close: Vec<univariate_solvers::RootEstimate> = univariate_solvers::all_roots(|x: f64| (x - 0.5)*(x - 0.51), -3.0, 3.0, 50, tol, max_iter).unwrap();// Both roots lie between two samples
+    let x_roots_close: na::DVector<f64> = na::DVector::from_iterator(roots_close.len(), roots_close.iter().map(|r| r.x));
+    let f_roots_close: na::DVector<f64> = na::DVector::from_iterator(roots_close.len(), roots_close.iter().map(|r| r.f_x));
+    num_tests_passed += check_result_vector(&x_roots_close, &na::DVector::from_vec(vec![0.5, 0.51]), tol, "All roots (close roots)", verbose);
+    num_tests_passed += check_result_vector(&f_roots_close, &na::DVector::zeros(2), tol, "All roots (close roots, f(x))", verbose);
 
     print_test_results(num_tests_passed, num_tests_total);
 }
@@ -147,12 +388,148 @@ fn test_univariate_optimizers(verbose: bool) {
     let max_iter : u32 = 100;
     let dx_num :   f64 = 1e-6;
     let mut num_tests_passed : u32 = 0;
-    let num_tests_total :      u32 = 1;
+    let num_tests_total :      u32 = 6;
 
     let x_mathematica:    f64 = -4.54295618675514754103476876324;// 30 digits of precision
     let y_mathematica:    f64 = -0.206327079359226884630654987440;// 30 digits of precision
-    let x_golden_section: f64 = univariate_minimizers::golden_section_minimize(&(fct as fn(f64) -> f64), -7.0, -1.0, tol);
-    num_tests_passed += check_result(x_golden_section, x_mathematica, tol*1e2, "Golden section search", verbose);
+    let sol_golden_section = univariate_minimizers::golden_section_minimize(fct, -7.0, -1.0, tol).unwrap();
+    num_tests_passed += check_result(sol_golden_section.x, sol_golden_section.f_x, x_mathematica, tol*1e2, "Golden section search", verbose);
+    let sol_brent = univariate_minimizers::brent_minimize(fct, -7.0, -1.0, tol).unwrap();
+    num_tests_passed += check_result(sol_brent.x, sol_brent.f_x, x_mathematica, tol*1e2, "Brent's minimization", verbose);
+    let sol_cubic_lagrange = univariate_minimizers::cubic_lagrange_minimize(fct, -7.0, -1.0, tol).unwrap();
+    num_tests_passed += check_result(sol_cubic_lagrange.x, sol_cubic_lagrange.f_x, x_mathematica, tol*1e2, "Cubic Lagrange minimization", verbose);
+
+    // Without bracket : the minimum is searched downhill from a single point
+    let bracket: univariate_minimizers::MinimumBracket = univariate_minimizers::bracket_minimum(fct, -5.0, None).unwrap();
+    num_tests_passed += check_minimum_bracket(&bracket, "Minimum bracketing", verbose);
+    let sol_golden_auto = univariate_minimizers::golden_section_minimize_auto(fct, -5.0, None, tol).unwrap();
+    let sol_brent_auto = univariate_minimizers::brent_minimize_auto(fct, -5.0, None, tol).unwrap();
+    num_tests_passed += check_result(sol_golden_auto.x, sol_golden_auto.f_x, x_mathematica, tol*1e2, "Golden section search (auto)", verbose);
+    num_tests_passed += check_result(sol_brent_auto.x, sol_brent_auto.f_x, x_mathematica, tol*1e2, "Brent's minimization (auto)", verbose);
+    print_test_results(num_tests_passed, num_tests_total);
+}
+
+/// Concatenates the real and imaginary parts of complex roots, to compare them with check_result_vector
+fn complex_roots_vector(roots: &[na::Complex<f64>]) -> na::DVector<f64> {
+    na::DVector::from_iterator(2*roots.len(), roots.iter().map(|z| z.re).chain(roots.iter().map(|z| z.im)))
+}
+
+fn test_polynomial_roots(verbose: bool) {
+    println!("Testing polynomial root finders.");
+    let tol :      f64 = 1e-12;
+    let max_iter : u32 = 100;
+    let mut num_tests_passed : u32 = 0;
+    let num_tests_total :      u32 = 6;
+
+    // x^3 - 2x - 5 : one real root and a pair of complex conjugate roots
+    let wallis: [f64; 4] = [-5.0, -2.0, 0.0, 1.0];
+    let x_wallis: f64 = 2.0945514815423265;// Rounded to double precision
+    let re_wallis: f64 = -1.0472757407711633;// Rounded to double precision
+    let im_wallis: f64 = 1.1359398890889283;// Rounded to double precision
+    let roots_wallis_true: na::DVector<f64> = complex_roots_vector(&[na::Complex::new(re_wallis, -im_wallis), na::Complex::new(re_wallis, im_wallis), na::Complex::new(x_wallis, 0.0)]);
+    let roots_wallis = polynomial::polynomial_roots(&wallis, false, tol, max_iter).unwrap();
+    let roots_wallis_polished = polynomial::polynomial_roots(&wallis, true, tol, max_iter).unwrap();
+    num_tests_passed += check_result_vector(&complex_roots_vector(&roots_wallis), &roots_wallis_true, 1e-10, "Companion matrix", verbose);
+    num_tests_passed += check_result_vector(&complex_roots_vector(&roots_wallis_polished), &roots_wallis_true, tol, "Companion matrix (polished)", verbose);
+
+    // (x - 1)(x - 2)(x - 3)(x - 4), and x^2*(x^2 + 1) which has a double root at zero
+    let roots_wilkinson = polynomial::polynomial_roots(&[24.0, -50.0, 35.0, -10.0, 1.0], true, tol, max_iter).unwrap();
+    let roots_zero = polynomial::polynomial_roots(&[0.0, 0.0, 1.0, 0.0, 1.0], true, tol, max_iter).unwrap();
+    num_tests_passed += check_result_vector(&complex_roots_vector(&roots_wilkinson), &complex_roots_vector(&[1.0, 2.0, 3.0, 4.0].map(|x| na::Complex::new(x, 0.0))), tol, "Companion matrix (real roots)", verbose);
+    // (x - 1)^3 : the triple root is only found to about eps^(1/3), and is left unpolished
+    let roots_triple = polynomial::polynomial_roots(&[-1.0, 3.0, -3.0, 1.0], true, tol, max_iter).unwrap();
+    num_tests_passed += check_result_vector(&complex_roots_vector(&roots_triple), &complex_roots_vector(&[na::Complex::new(1.0, 0.0); 3]), 1e-4, "Companion matrix (triple root)", verbose);
+    num_tests_passed += check_result_vector(&complex_roots_vector(&roots_zero), &complex_roots_vector(&[na::Complex::new(0.0, -1.0), na::Complex::new(0.0, 0.0), na::Complex::new(0.0, 0.0), na::Complex::new(0.0, 1.0)]), tol, "Companion matrix (zero roots)", verbose);
+
+    // Deflation of x^3 - 2x - 5 by its complex roots leaves x - x_wallis
+    let deflated: Vec<f64> = polynomial::polynomial_deflate(&wallis, na::Complex::new(re_wallis, im_wallis));
+    num_tests_passed += check_result_vector(&na::DVector::from_vec(deflated), &na::DVector::from_vec(vec![-x_wallis, 1.0]), 1e-10, "Polynomial deflation", verbose);
+    print_test_results(num_tests_passed, num_tests_total);
+}
+
+/// e^z - z, whose root closest to 0.3 + 1.3i is 0.318131505204764135312654251588 + 1.33723570143068940890116214319i
+fn fct_complex(z: na::Complex<f64>) -> na::Complex<f64> {
+    z.exp() - z
+}
+
+fn dfct_complex(z: na::Complex<f64>) -> na::Complex<f64> {
+    z.exp() - 1.0
+}
+
+fn test_complex_solvers(verbose: bool) {
+    println!("Testing complex numerical solvers.");
+    let tol :      f64 = 1e-12;
+    let max_iter : u32 = 100;
+    let dx_num :   f64 = 1e-6;
+    let mut num_tests_passed : u32 = 0;
+    let num_tests_total :      u32 = 4;
+
+    let z0: na::Complex<f64> = na::Complex::new(0.0, 1.0);
+    let z_mathematica: na::Complex<f64> = na::Complex::new(0.31813150520476413, 1.3372357014306895);// Rounded to double precision
+    let z_newton = complex_solvers::complex_newton_solve(fct_complex, dfct_complex, z0, tol, max_iter).unwrap().x;
+    let z_newton_num = complex_solvers::complex_newton_solve_num(fct_complex, z0, tol, dx_num, max_iter).unwrap().x;
+    let z_muller = complex_solvers::muller_solve(fct_complex, na::Complex::new(0.0, 0.0), na::Complex::new(0.5, 0.5), z0, tol, max_iter).unwrap().x;
+    num_tests_passed += check_result_vector(&complex_roots_vector(&[z_newton]), &complex_roots_vector(&[z_mathematica]), tol*1e2, "Complex Newton's method", verbose);
+    num_tests_passed += check_result_vector(&complex_roots_vector(&[z_newton_num]), &complex_roots_vector(&[z_mathematica]), tol*1e2, "Complex Newton's method (num)", verbose);
+    num_tests_passed += check_result_vector(&complex_roots_vector(&[z_muller]), &complex_roots_vector(&[z_mathematica]), tol*1e2, "Muller's method", verbose);
+
+    // Complex root of a real polynomial, from real starting points
+    let z_muller_real = complex_solvers::muller_solve(|z: na::Complex<f64>| z*z + 1.0, na::Complex::new(-1.0, 0.0), na::Complex::new(0.0, 0.0), na::Complex::new(1.0, 0.0), tol, max_iter).unwrap().x;
+    num_tests_passed += check_result_vector(&complex_roots_vector(&[na::Complex::new(z_muller_real.re, z_muller_real.im.abs())]), &complex_roots_vector(&[na::Complex::new(0.0, 1.0)]), tol*1e2, "Muller's method (real start)", verbose);// Either i or -i
+    print_test_results(num_tests_passed, num_tests_total);
+}
+
+fn test_multivariate_solvers(verbose: bool) {
+    println!("Testing multivariate numerical solvers.");
+    let tol :      f64 = 1e-10;
+    let max_iter : u32 = 100;
+    let dx_num :   f64 = 1e-7;
+    let mut num_tests_passed : u32 = 0;
+    let num_tests_total :      u32 = 15;
+
+    let x0_rosenbrock: na::DVector<f64> = na::DVector::from_vec(vec![-1.2, 1.0]);
+    let x_true_rosenbrock: na::DVector<f64> = na::DVector::from_vec(vec![1.0, 1.0]);
+    let x0_circle: na::DVector<f64> = na::DVector::from_vec(vec![3.0, 0.5]);
+    let x_true_circle: na::DVector<f64> = na::DVector::from_vec(vec![2.0, 1.0]);
+
+    let x_newton: na::DVector<f64> = multivariate_solvers::newton_system_solve(rosenbrock_system, jac_rosenbrock_system, &x0_rosenbrock, tol, max_iter).unwrap().x;
+    let x_newton_num: na::DVector<f64> = multivariate_solvers::newton_system_solve_num(rosenbrock_system, &x0_rosenbrock, tol, dx_num, max_iter).unwrap().x;
+    let x_newton_circle: na::DVector<f64> = multivariate_solvers::newton_system_solve(circle_hyperbola_system, jac_circle_hyperbola_system, &x0_circle, tol, max_iter).unwrap().x;
+    let x_newton_circle_num: na::DVector<f64> = multivariate_solvers::newton_system_solve_num(circle_hyperbola_system, &x0_circle, tol, dx_num, max_iter).unwrap().x;
+    num_tests_passed += check_result_vector(&x_newton, &x_true_rosenbrock, tol, "Newton-Raphson (system)", verbose);
+    num_tests_passed += check_result_vector(&x_newton_num, &x_true_rosenbrock, tol, "Newton-Raphson (system, num)", verbose);
+    num_tests_passed += check_result_vector(&x_newton_circle, &x_true_circle, tol, "Newton-Raphson (circle)", verbose);
+    num_tests_passed += check_result_vector(&x_newton_circle_num, &x_true_circle, tol, "Newton-Raphson (circle, num)", verbose);
+    // The root (sqrt(2), 1) is not representable and F is scaled by 1e10, so ||F|| cannot fall below tol in double precision : the steps vanish first
+    let scaled_system = |x: &na::DVector<f64>| 1e10*na::DVector::from_vec(vec![x[0].powi(2) - 2.0, x[1] - 1.0]);
+    let jac_scaled_system = |x: &na::DVector<f64>| 1e10*na::DMatrix::from_row_slice(2, 2, &[2.0*x[0], 0.0, 0.0, 1.0]);
+    let err_newton_scaled = multivariate_solvers::newton_system_solve(scaled_system, jac_scaled_system, &x0_circle, tol, max_iter);
+    num_tests_passed += check_error(err_newton_scaled, solver_error::SolverError::NoProgress, "Newton-Raphson (unreachable)", verbose);
+
+    let x_broyden_good: na::DVector<f64> = multivariate_solvers::broyden_solve(rosenbrock_system, &x0_rosenbrock, multivariate_solvers::BroydenUpdate::Good, tol, dx_num, max_iter).unwrap().x;
+    let x_broyden_bad: na::DVector<f64> = multivariate_solvers::broyden_solve(rosenbrock_system, &x0_rosenbrock, multivariate_solvers::BroydenUpdate::Bad, tol, dx_num, max_iter).unwrap().x;
+    let x_broyden_good_circle: na::DVector<f64> = multivariate_solvers::broyden_solve(circle_hyperbola_system, &x0_circle, multivariate_solvers::BroydenUpdate::Good, tol, dx_num, max_iter).unwrap().x;
+    let x_broyden_bad_circle: na::DVector<f64> = multivariate_solvers::broyden_solve(circle_hyperbola_system, &x0_circle, multivariate_solvers::BroydenUpdate::Bad, tol, dx_num, max_iter).unwrap().x;
+    num_tests_passed += check_result_vector(&x_broyden_good, &x_true_rosenbrock, tol, "Good Broyden", verbose);
+    num_tests_passed += check_result_vector(&x_broyden_bad, &x_true_rosenbrock, tol, "Bad Broyden", verbose);
+    num_tests_passed += check_result_vector(&x_broyden_good_circle, &x_true_circle, tol, "Good Broyden (circle)", verbose);
+    num_tests_passed += check_result_vector(&x_broyden_bad_circle, &x_true_circle, tol, "Bad Broyden (circle)", verbose);
+    let err_broyden_scaled = multivariate_solvers::broyden_solve(scaled_system, &x0_circle, multivariate_solvers::BroydenUpdate::Good, tol, dx_num, max_iter);
+    num_tests_passed += check_error(err_broyden_scaled, solver_error::SolverError::NoProgress, "Good Broyden (unreachable)", verbose);
+
+    let x0_powell: na::DVector<f64> = na::DVector::from_vec(vec![0.0, 1.0]);
+    let x_true_powell: na::DVector<f64> = na::DVector::from_vec(vec![1.098159329699817e-5, 9.106146739866524]);
+    let max_iter_powell: u32 = 1000;
+    let x_hybrid: na::DVector<f64> = multivariate_solvers::powell_hybrid_solve(rosenbrock_system, jac_rosenbrock_system, &x0_rosenbrock, tol, max_iter).unwrap().x;
+    let x_hybrid_num: na::DVector<f64> = multivariate_solvers::powell_hybrid_solve_num(rosenbrock_system, &x0_rosenbrock, tol, dx_num, max_iter).unwrap().x;
+    let x_hybrid_powell: na::DVector<f64> = multivariate_solvers::powell_hybrid_solve(powell_badly_scaled_system, jac_powell_badly_scaled_system, &x0_powell, tol, max_iter_powell).unwrap().x;
+    let x_hybrid_powell_num: na::DVector<f64> = multivariate_solvers::powell_hybrid_solve_num(powell_badly_scaled_system, &x0_powell, tol, dx_num, max_iter_powell).unwrap().x;
+    num_tests_passed += check_result_vector(&x_hybrid, &x_true_rosenbrock, tol, "Powell hybrid", verbose);
+    num_tests_passed += check_result_vector(&x_hybrid_num, &x_true_rosenbrock, tol, "Powell hybrid (num)", verbose);
+    num_tests_passed += check_result_vector(&x_hybrid_powell, &x_true_powell, 1e-8, "Powell hybrid (badly scaled)", verbose);
+    num_tests_passed += check_result_vector(&x_hybrid_powell_num, &x_true_powell, 1e-8, "Powell hybrid (bad. scal. num)", verbose);
+    let err_hybrid_scaled = multivariate_solvers::powell_hybrid_solve(scaled_system, jac_scaled_system, &x0_circle, tol, max_iter);
+    num_tests_passed += check_error(err_hybrid_scaled, solver_error::SolverError::NoProgress, "Powell hybrid (unreachable)", verbose);
     print_test_results(num_tests_passed, num_tests_total);
 }
 
@@ -161,33 +538,141 @@ fn test_multivariate_optimizers(verbose: bool) {
     let max_iter : u32 = 1000;
     let dx_num :   f64 = 1e-6;
     let mut num_tests_passed : u32 = 0;
-    let num_tests_total :      u32 = 1;
+    let num_tests_total :      u32 = 22;
 
     let tol_x:      f64 = 1e-4;
     let tol_f_x:    f64 = 1e-6;
     
     let x_true:        na::DVector<f64> = na::DVector::from_vec(vec![1.,1.]);
     let f_x_true:      f64 = rosenbrock(&x_true);
-    let sol_nelder_mead: (na::DVector<f64>, f64) = nelder_mead::nelder_mead_minimize(&(rosenbrock as fn(&na::DVector<f64>) -> f64), &na::DVector::from_vec(vec![2.0,-1.0]), 0.1, tol, max_iter, false);
+    let sol_nelder_mead: solver_result::SolverResult<na::DVector<f64>> = nelder_mead::nelder_mead_minimize(rosenbrock, &na::DVector::from_vec(vec![2.0,-1.0]), 0.1, tol, max_iter, false).unwrap();
     // num_tests_passed += check_result(x_nelder_mead, x_true, tol*1e2, "Nelder-Mead", verbose);
-    num_tests_passed += check_result_optim(&sol_nelder_mead.0, sol_nelder_mead.1, &x_true, f_x_true, tol_x, tol_f_x, "Nelder-Mead", verbose);
+    num_tests_passed += check_result_optim(&sol_nelder_mead.x, sol_nelder_mead.f_x, &x_true, f_x_true, tol_x, tol_f_x, "Nelder-Mead", verbose);
+
+    let x0: na::DVector<f64> = na::DVector::from_vec(vec![2.0,-1.0]);
+    let sol_bfgs = quasi_newton::bfgs_minimize(rosenbrock, grad_rosenbrock, &x0, tol, max_iter).unwrap();
+    let sol_bfgs_num = quasi_newton::bfgs_minimize_num(rosenbrock, &x0, tol, dx_num, max_iter).unwrap();
+    num_tests_passed += check_result_optim(&sol_bfgs.x, sol_bfgs.f_x, &x_true, f_x_true, tol_x, tol_f_x, "BFGS", verbose);
+    num_tests_passed += check_result_optim(&sol_bfgs_num.x, sol_bfgs_num.f_x, &x_true, f_x_true, tol_x, tol_f_x, "BFGS (num)", verbose);
+    // With a zero tolerance, the line search eventually fails at the limit of the machine precision : the best point is still returned
+    let sol_bfgs_exhausted = quasi_newton::bfgs_minimize(rosenbrock, grad_rosenbrock, &x0, 0.0, max_iter).unwrap();
+    num_tests_passed += check_termination(sol_bfgs_exhausted.termination, sol_bfgs_exhausted.converged, solver_result::TerminationReason::LineSearchFailure, "BFGS (line search failure)", verbose);
+
+    let m: usize = 5;
+    let sol_lbfgs = quasi_newton::lbfgs_minimize(rosenbrock, grad_rosenbrock, &x0, m, tol, max_iter).unwrap();
+    let sol_lbfgs_num = quasi_newton::lbfgs_minimize_num(rosenbrock, &x0, m, tol, dx_num, max_iter).unwrap();
+    num_tests_passed += check_result_optim(&sol_lbfgs.x, sol_lbfgs.f_x, &x_true, f_x_true, tol_x, tol_f_x, "L-BFGS", verbose);
+    num_tests_passed += check_result_optim(&sol_lbfgs_num.x, sol_lbfgs_num.f_x, &x_true, f_x_true, tol_x, tol_f_x, "L-BFGS (num)", verbose);
+    let sol_lbfgs_exhausted = quasi_newton::lbfgs_minimize(rosenbrock, grad_rosenbrock, &x0, m, 0.0, max_iter).unwrap();
+    num_tests_passed += check_termination(sol_lbfgs_exhausted.termination, sol_lbfgs_exhausted.converged, solver_result::TerminationReason::LineSearchFailure, "L-BFGS (line search failure)", verbose);
+
+    // High-dimensional problem, starting from the classical point (-1.2, 1, -1.2, 1, ...)
+    let n_dims: usize = 1000;
+    let x0_ext: na::DVector<f64> = na::DVector::from_fn(n_dims, |i, _| if i % 2 == 0 { -1.2 } else { 1.0 });
+    let x_true_ext: na::DVector<f64> = na::DVector::from_element(n_dims, 1.0);
+    let sol_lbfgs_ext = quasi_newton::lbfgs_minimize(rosenbrock_extended, grad_rosenbrock_extended, &x0_ext, m, tol, max_iter).unwrap();
+    num_tests_passed += check_result_optim(&sol_lbfgs_ext.x, sol_lbfgs_ext.f_x, &x_true_ext, 0.0, tol_x, tol_f_x, "L-BFGS (n = 1000)", false);// Not verbose, x has 1000 components
+
+    // Bound-constrained problem : x[0] <= 0.5 excludes the unconstrained minimum, the constrained one is (0.5, 0.25)
+    let lb: na::DVector<f64> = na::DVector::from_vec(vec![f64::NEG_INFINITY, f64::NEG_INFINITY]);
+    let ub: na::DVector<f64> = na::DVector::from_vec(vec![0.5, f64::INFINITY]);
+    let ub_free: na::DVector<f64> = na::DVector::from_element(2, f64::INFINITY);
+    let x_true_bounded: na::DVector<f64> = na::DVector::from_vec(vec![0.5, 0.25]);
+    let f_x_true_bounded: f64 = rosenbrock(&x_true_bounded);
+    let sol_lbfgsb = quasi_newton::lbfgsb_minimize(rosenbrock, grad_rosenbrock, &x0, &lb, &ub, m, tol, max_iter).unwrap();
+    let sol_lbfgsb_num = quasi_newton::lbfgsb_minimize_num(rosenbrock, &x0, &lb, &ub, m, tol, dx_num, max_iter).unwrap();
+    let sol_lbfgsb_free = quasi_newton::lbfgsb_minimize(rosenbrock, grad_rosenbrock, &x0, &lb, &ub_free, m, tol, max_iter).unwrap();
+    num_tests_passed += check_result_optim(&sol_lbfgsb.x, sol_lbfgsb.f_x, &x_true_bounded, f_x_true_bounded, tol_x, tol_f_x, "L-BFGS-B", verbose);
+    num_tests_passed += check_result_optim(&sol_lbfgsb_num.x, sol_lbfgsb_num.f_x, &x_true_bounded, f_x_true_bounded, tol_x, tol_f_x, "L-BFGS-B (num)", verbose);
+    num_tests_passed += check_result_optim(&sol_lbfgsb_free.x, sol_lbfgsb_free.f_x, &x_true, f_x_true, tol_x, tol_f_x, "L-BFGS-B (unbounded)", verbose);
+
+    let sol_cg_fr = conjugate_gradient::conjugate_gradient_minimize(rosenbrock, grad_rosenbrock, &x0, conjugate_gradient::ConjugateGradientBeta::FletcherReeves, tol, max_iter).unwrap();
+    let sol_cg_pr = conjugate_gradient::conjugate_gradient_minimize(rosenbrock, grad_rosenbrock, &x0, conjugate_gradient::ConjugateGradientBeta::PolakRibierePlus, tol, max_iter).unwrap();
+    let sol_cg_hz = conjugate_gradient::conjugate_gradient_minimize(rosenbrock, grad_rosenbrock, &x0, conjugate_gradient::ConjugateGradientBeta::HagerZhang, tol, max_iter).unwrap();
+    let sol_cg_num = conjugate_gradient::conjugate_gradient_minimize_num(rosenbrock, &x0, conjugate_gradient::ConjugateGradientBeta::PolakRibierePlus, tol, dx_num, max_iter).unwrap();
+    let sol_cg_ext = conjugate_gradient::conjugate_gradient_minimize(rosenbrock_extended, grad_rosenbrock_extended, &x0_ext, conjugate_gradient::ConjugateGradientBeta::HagerZhang, tol, max_iter).unwrap();
+    num_tests_passed += check_result_optim(&sol_cg_fr.x, sol_cg_fr.f_x, &x_true, f_x_true, tol_x, tol_f_x, "CG (Fletcher-Reeves)", verbose);
+    num_tests_passed += check_result_optim(&sol_cg_pr.x, sol_cg_pr.f_x, &x_true, f_x_true, tol_x, tol_f_x, "CG (Polak-Ribiere+)", verbose);
+    num_tests_passed += check_result_optim(&sol_cg_hz.x, sol_cg_hz.f_x, &x_true, f_x_true, tol_x, tol_f_x, "CG (Hager-Zhang)", verbose);
+    num_tests_passed += check_result_optim(&sol_cg_num.x, sol_cg_num.f_x, &x_true, f_x_true, tol_x, tol_f_x, "CG (Polak-Ribiere+, num)", verbose);
+    // The tolerance cannot be reached in double precision : the line search eventually fails and the best point is returned
+    let sol_cg_exhausted = conjugate_gradient::conjugate_gradient_minimize(rosenbrock, grad_rosenbrock, &x0, conjugate_gradient::ConjugateGradientBeta::FletcherReeves, 1e-14, max_iter).unwrap();
+    num_tests_passed += check_result_optim(&sol_cg_exhausted.x, sol_cg_exhausted.f_x, &x_true, f_x_true, tol_x, tol_f_x, "CG (line search failure)", verbose);
+    num_tests_passed += check_termination(sol_cg_exhausted.termination, sol_cg_exhausted.converged, solver_result::TerminationReason::LineSearchFailure, "CG (line search failure, report)", verbose);
+    num_tests_passed += check_result_optim(&sol_cg_ext.x, sol_cg_ext.f_x, &x_true_ext, 0.0, tol_x, tol_f_x, "CG (Hager-Zhang, n = 1000)", false);// Not verbose, x has 1000 components
+
+    let x0_indefinite: na::DVector<f64> = na::DVector::from_vec(vec![0.0, 1.0]);// The Hessian is indefinite at this point
+    let sol_trust_region = trust_region::newton_trust_region_minimize(rosenbrock, grad_rosenbrock, hess_rosenbrock, &x0, tol, max_iter).unwrap();
+    let sol_trust_region_num = trust_region::newton_trust_region_minimize_num(rosenbrock, &x0, tol, dx_num, max_iter).unwrap();
+    let sol_trust_region_indefinite = trust_region::newton_trust_region_minimize(rosenbrock, grad_rosenbrock, hess_rosenbrock, &x0_indefinite, tol, max_iter).unwrap();
+    num_tests_passed += check_result_optim(&sol_trust_region.x, sol_trust_region.f_x, &x_true, f_x_true, tol_x, tol_f_x, "Newton trust-region", verbose);
+    num_tests_passed += check_result_optim(&sol_trust_region_num.x, sol_trust_region_num.f_x, &x_true, f_x_true, tol_x, tol_f_x, "Newton trust-region (num)", verbose);
+    num_tests_passed += check_result_optim(&sol_trust_region_indefinite.x, sol_trust_region_indefinite.f_x, &x_true, f_x_true, tol_x, tol_f_x, "Newton trust-region (indefinite)", verbose);
+    // The minimum at (sqrt(2), 1) is not representable and the gradient is scaled by 1e10, so the gradient cannot fall below tol in double precision
+    let scaled_objective = |x: &na::DVector<f64>| 1e10*((x[0].powi(2) - 2.0).powi(2) + (x[1] - 1.0).powi(2));
+    let grad_scaled_objective = |x: &na::DVector<f64>| 1e10*na::DVector::from_vec(vec![4.0*x[0]*(x[0].powi(2) - 2.0), 2.0*(x[1] - 1.0)]);
+    let hess_scaled_objective = |x: &na::DVector<f64>| 1e10*na::DMatrix::from_row_slice(2, 2, &[12.0*x[0].powi(2) - 8.0, 0.0, 0.0, 2.0]);
+    let sol_trust_region_exhausted = trust_region::newton_trust_region_minimize(scaled_objective, grad_scaled_objective, hess_scaled_objective, &x0, tol, max_iter).unwrap();
+    num_tests_passed += check_termination(sol_trust_region_exhausted.termination, sol_trust_region_exhausted.converged, solver_result::TerminationReason::TrustRegionCollapse, "Newton trust-region (collapse)", verbose);
     print_test_results(num_tests_passed, num_tests_total);
 }
 
-fn test_particle_swarm_debug() {
+fn test_particle_swarm(verbose: bool) {
+    let mut num_tests_passed : u32 = 0;
+    let num_tests_total :      u32 = 1;
+
     let n_particles: u32 = 10;
     let lb: na::DVector<f64> = na::DVector::from_vec(vec![-5.0,-5.0]);
     let ub: na::DVector<f64> = na::DVector::from_vec(vec![5.0,5.0]);
     let tol: f64 = 1e-6;
     let n_iter_max: u32 = 1000;
     let rng_seed: u32 = 69;
-    let x: f64 = particle_swarm_optimization::particle_swarm_minimize(&rosenbrock, n_particles, &lb, &ub, tol, n_iter_max, rng_seed);
-    println!("x = {}", x);
+
+    let tol_x:      f64 = 1e-2;
+    let tol_f_x:    f64 = 1e-4;
+
+    let x_true:        na::DVector<f64> = na::DVector::from_vec(vec![1.,1.]);
+    let f_x_true:      f64 = rosenbrock(&x_true);
+    let sol_pso = particle_swarm_optimization::particle_swarm_minimize(rosenbrock, n_particles, &lb, &ub, tol, n_iter_max, rng_seed).unwrap();
+    num_tests_passed += check_result_optim(&sol_pso.x, sol_pso.f_x, &x_true, f_x_true, tol_x, tol_f_x, "Particle swarm", verbose);
+    print_test_results(num_tests_passed, num_tests_total);
+}
+
+fn test_differential_evolution(verbose: bool) {
+    let mut num_tests_passed : u32 = 0;
+    let num_tests_total :      u32 = 5;
+
+    let n_population: u32 = 20;
+    let lb: na::DVector<f64> = na::DVector::from_vec(vec![-5.0,-5.0]);
+    let ub: na::DVector<f64> = na::DVector::from_vec(vec![5.0,5.0]);
+    let mutation_factor: f64 = 0.8;
+    let crossover_rate: f64 = 0.9;
+    let tol: f64 = 1e-10;
+    let n_iter_max: u32 = 1000;
+    let rng_seed: u32 = 69;
+
+    let tol_x:      f64 = 1e-2;
+    let tol_f_x:    f64 = 1e-4;
+
+    let x_true:        na::DVector<f64> = na::DVector::from_vec(vec![1.,1.]);
+    let f_x_true:      f64 = rosenbrock(&x_true);
+    let sol_rand1bin = differential_evolution::differential_evolution_minimize(rosenbrock, n_population, &lb, &ub, differential_evolution::DifferentialEvolutionStrategy::Rand1Bin, mutation_factor, crossover_rate, tol, n_iter_max, rng_seed, false).unwrap();
+    let sol_best1bin = differential_evolution::differential_evolution_minimize(rosenbrock, n_population, &lb, &ub, differential_evolution::DifferentialEvolutionStrategy::Best1Bin, mutation_factor, crossover_rate, tol, n_iter_max, rng_seed, false).unwrap();
+    let sol_current_to_best = differential_evolution::differential_evolution_minimize(rosenbrock, n_population, &lb, &ub, differential_evolution::DifferentialEvolutionStrategy::CurrentToBest1Bin, mutation_factor, crossover_rate, tol, n_iter_max, rng_seed, false).unwrap();
+    // Loose population tolerance, so that the polish has to do the final refinement
+    let sol_unpolished = differential_evolution::differential_evolution_minimize(rosenbrock, n_population, &lb, &ub, differential_evolution::DifferentialEvolutionStrategy::Rand1Bin, mutation_factor, crossover_rate, 1e-3, n_iter_max, rng_seed, false).unwrap();
+    let sol_polish = differential_evolution::differential_evolution_minimize(rosenbrock, n_population, &lb, &ub, differential_evolution::DifferentialEvolutionStrategy::Rand1Bin, mutation_factor, crossover_rate, 1e-3, n_iter_max, rng_seed, true).unwrap();
+    num_tests_passed += check_result_optim(&sol_rand1bin.x, sol_rand1bin.f_x, &x_true, f_x_true, tol_x, tol_f_x, "Diff. evolution (rand/1/bin)", verbose);
+    num_tests_passed += check_result_optim(&sol_best1bin.x, sol_best1bin.f_x, &x_true, f_x_true, tol_x, tol_f_x, "Diff. evolution (best/1/bin)", verbose);
+    num_tests_passed += check_result_optim(&sol_current_to_best.x, sol_current_to_best.f_x, &x_true, f_x_true, tol_x, tol_f_x, "Diff. evolution (cur-to-best)", verbose);
+    num_tests_passed += check_result_optim(&sol_polish.x, sol_polish.f_x, &x_true, f_x_true, tol_x, tol_f_x, "Diff. evolution (polished)", verbose);
+    num_tests_passed += check_improvement(sol_polish.f_x, sol_unpolished.f_x, "Diff. evolution (polish gain)", verbose);
+    print_test_results(num_tests_passed, num_tests_total);
 }
 
 fn test_non_linear_lsqr_solvers(verbose: bool) {
     let mut num_tests_passed : u32 = 0;
-    let num_tests_total :      u32 = 1;
+    let num_tests_total :      u32 = 4;
 
     let tol:        f64 = 1e-6;
     let dx_num:     f64 = 1e-7;
@@ -201,15 +686,25 @@ fn test_non_linear_lsqr_solvers(verbose: bool) {
         for i in 0..x.nrows() {
             y[i] = (beta[0] * x[i])/(beta[1] + x[i]);
         }
-        return y;
+        y
     }
 
     let beta_numpy: na::DVector<f64> = na::DVector::from_vec(vec![0.3618368601272124, 0.5562663893098662]);
     let mut beta_gauss_newton: na::DVector<f64> = na::DVector::from_vec(vec![0.9, 0.2]);
-    beta_gauss_newton = non_linear_least_squares::gauss_newton_lsqr(&xp, &yp, &fct_lsqr, &beta_gauss_newton, tol, n_iter_max, dx_num, false);
+    beta_gauss_newton = non_linear_least_squares::gauss_newton_lsqr(&xp, &yp, &fct_lsqr, &beta_gauss_newton, tol, n_iter_max, dx_num, false).unwrap().x;
     // println!("beta_gauss_newton = {}\tf(beta_gauss_newton) - yp = {}", beta_gauss_newton, fct_lsqr(&xp, &beta_gauss_newton) - yp);
 
     num_tests_passed += check_result_vector(&beta_gauss_newton, &beta_numpy, tol, "Gauss-Newton least squares", verbose);
+
+    // Levenberg-Marquardt from the same initial guess, and from a poor one on which Gauss-Newton fails
+    let beta_bad: na::DVector<f64> = na::DVector::from_vec(vec![10.0, 50.0]);
+    let tol_lm:   f64 = 1e-10;// The gradient test of Levenberg-Marquardt stops earlier than the step test of Gauss-Newton
+    let beta_levenberg_marquardt: na::DVector<f64> = non_linear_least_squares::levenberg_marquardt_lsqr(&xp, &yp, &fct_lsqr, &na::DVector::from_vec(vec![0.9, 0.2]), tol_lm, n_iter_max, dx_num, false).unwrap().x;
+    let beta_levenberg_marquardt_bad: na::DVector<f64> = non_linear_least_squares::levenberg_marquardt_lsqr(&xp, &yp, &fct_lsqr, &beta_bad, tol_lm, n_iter_max, dx_num, false).unwrap().x;
+    num_tests_passed += check_result_vector(&beta_levenberg_marquardt, &beta_numpy, tol, "Levenberg-Marquardt", verbose);
+    num_tests_passed += check_result_vector(&beta_levenberg_marquardt_bad, &beta_numpy, tol, "Levenberg-Marquardt (bad x0)", verbose);
+    let err_gauss_newton_bad = non_linear_least_squares::gauss_newton_lsqr(&xp, &yp, &fct_lsqr, &beta_bad, tol, n_iter_max, dx_num, false);
+    num_tests_passed += check_error(err_gauss_newton_bad, solver_error::SolverError::SingularJacobian, "Gauss-Newton (bad x0)", verbose);
     print_test_results(num_tests_passed, num_tests_total);
 }
 
@@ -218,7 +713,11 @@ fn main() {
     let verbose : bool = true;
     test_univariate_solvers(verbose);
     test_univariate_optimizers(verbose);
+    test_polynomial_roots(verbose);
+    test_complex_solvers(verbose);
+    test_multivariate_solvers(verbose);
     test_multivariate_optimizers(verbose);
-    // test_particle_swarm_debug();// debug
+    test_particle_swarm(verbose);
+    test_differential_evolution(verbose);
     test_non_linear_lsqr_solvers(verbose);
 }
@@ -0,0 +1,401 @@
+extern crate nalgebra as na;
+
+use crate::finite_differences;
+use crate::solver_error::SolverError;
+use crate::solver_result::{SolverResult, TerminationReason};
+
+/// Checks that all the components of a vector are finite.
+/// @param v vector to check
+/// @return true if all the components of v are finite
+fn is_finite(v: &na::DVector<f64>) -> bool {
+    v.iter().all(|vi| vi.is_finite())
+}
+
+/// Backtracking line search along a descent direction of phi(x) = 0.5*||F(x)||^2, using the Armijo condition.
+/// @param f function to solve
+/// @param x current point
+/// @param phi 0.5*||F(x)||^2 at the current point
+/// @param dx search direction
+/// @param slope directional derivative of phi along dx (must be negative)
+/// @param tol tolerance on the step size
+/// @param n_fev number of function evaluations, incremented by the line search
+/// @return the new point, the function value at the new point and the step actually taken
+fn backtracking_line_search<F>(f: &F, x: &na::DVector<f64>, phi: f64, dx: &na::DVector<f64>, slope: f64, tol: f64, n_fev: &mut u32) -> (na::DVector<f64>, na::DVector<f64>, na::DVector<f64>)
+where F : Fn(&na::DVector<f64>) -> na::DVector<f64>
+{
+    let c: f64 = 1e-4;      // Sufficient decrease coefficient
+    let t_min_ratio: f64 = 0.1;// Smallest reduction of the step length allowed at each backtracking
+    let mut t: f64 = 1.0;
+    loop {
+        let step: na::DVector<f64> = t*dx;
+        let x_new: na::DVector<f64> = x + &step;
+        let f_x_new: na::DVector<f64> = f(&x_new);
+        *n_fev += 1;
+        let phi_new: f64 = if is_finite(&f_x_new) { 0.5*f_x_new.norm_squared() } else { f64::INFINITY };
+        // Accept the step if it decreases phi sufficiently, or if it becomes too small to matter
+        if phi_new <= phi + c*t*slope || step.norm() < tol {
+            return (x_new, f_x_new, step);
+        }
+        // Minimize the quadratic model of phi(t) built from phi(0), phi'(0) and phi(t)
+        let t_quad: f64 = if phi_new.is_finite() { -slope*t*t / (2.0*(phi_new - phi - slope*t)) } else { 0.0 };
+        t = f64::max(t_quad, t_min_ratio*t).min(0.5*t);
+    }
+}
+
+/// Newton-Raphson method with a backtracking line search, shared by newton_system_solve and newton_system_solve_num.
+/// @param f function to solve
+/// @param jac Jacobian matrix of f, given x and F(x)
+/// @param jac_fev number of evaluations of f made by each call to jac
+/// @param x0 initial guess
+/// @param tol tolerance on the norm of F and on the norm of the step
+/// @param max_iter maximum number of iterations
+/// @return solution report, where f_x is the norm of F at the solution
+fn newton_system<F, J>(f: F, jac: J, jac_fev: u32, x0: &na::DVector<f64>, tol: f64, max_iter: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError>
+where F : Fn(&na::DVector<f64>) -> na::DVector<f64>, J : Fn(&na::DVector<f64>, &na::DVector<f64>) -> na::DMatrix<f64>
+{
+    let mut x: na::DVector<f64> = x0.clone();
+    let mut f_x: na::DVector<f64> = f(&x);
+    let mut n_fev: u32 = 1;
+    if f_x.len() != x.len() {
+        return Err(SolverError::InvalidInput("F must have as many components as x"));
+    }
+    for iter in 0..max_iter {
+        if !is_finite(&f_x) {
+            return Err(SolverError::NonFiniteValue);
+        }
+        if f_x.norm() < tol {
+            return Ok(SolverResult::new(x, f_x.norm(), iter, n_fev, TerminationReason::FunctionTolerance));
+        }
+
+        // Solve J*dx = -F
+        let jac_x: na::DMatrix<f64> = jac(&x, &f_x);
+        n_fev += jac_fev;
+        let dx: na::DVector<f64> = match jac_x.lu().solve(&(-&f_x)) {
+            Some(dx) => dx,
+            None => return Err(SolverError::SingularJacobian),
+        };
+        if !is_finite(&dx) {
+            return Err(SolverError::SingularJacobian);
+        }
+
+        // Line search on phi = 0.5*||F||^2, whose slope along the Newton direction is -||F||^2
+        let phi: f64 = 0.5*f_x.norm_squared();
+        let (x_new, f_x_new, step) = backtracking_line_search(&f, &x, phi, &dx, -2.0*phi, tol, &mut n_fev);
+        x = x_new;
+        f_x = f_x_new;
+
+        if step.norm() < tol {
+            if !is_finite(&f_x) {
+                return Err(SolverError::NonFiniteValue);
+            }
+            // A vanishing step is only a success if F itself is close to zero, otherwise x is stuck away from any root
+            if f_x.norm() < tol {
+                return Ok(SolverResult::new(x, f_x.norm(), iter + 1, n_fev, TerminationReason::FunctionTolerance));
+            }
+            return Err(SolverError::NoProgress);
+        }
+    }
+    Err(SolverError::MaxIterations)
+}
+
+/// Newton-Raphson method for solving a system of non-linear equations F(x) = 0
+/// @param f function to solve
+/// @param jac Jacobian matrix of f, with jac(x)[(i, j)] = dF_i/dx_j
+/// @param x0 initial guess
+/// @param tol tolerance on the norm of F and on the norm of the step
+/// @param max_iter maximum number of iterations
+/// @return solution report, where f_x is the norm of F at the solution
+/// @note The Newton step is globalised with a backtracking line search on ||F||^2.
+/// @note If the step becomes smaller than tol while ||F|| is not, NoProgress is returned.
+pub fn newton_system_solve<F, J>(f: F, jac: J, x0: &na::DVector<f64>, tol: f64, max_iter: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError>
+where F : Fn(&na::DVector<f64>) -> na::DVector<f64>, J : Fn(&na::DVector<f64>) -> na::DMatrix<f64>
+{
+    newton_system(f, |x: &na::DVector<f64>, _f_x: &na::DVector<f64>| jac(x), 0, x0, tol, max_iter)
+}
+
+/// Newton-Raphson method for solving a system of non-linear equations F(x) = 0
+/// @param f function to solve
+/// @param x0 initial guess
+/// @param tol tolerance on the norm of F and on the norm of the step
+/// @param dx_num numerical differentiation step size
+/// @param max_iter maximum number of iterations
+/// @return solution report, where f_x is the norm of F at the solution
+/// @note The Newton step is globalised with a backtracking line search on ||F||^2.
+/// @note If the step becomes smaller than tol while ||F|| is not, NoProgress is returned.
+/// @note This method uses forward finite-differences to compute the Jacobian matrix.
+pub fn newton_system_solve_num<F>(f: F, x0: &na::DVector<f64>, tol: f64, dx_num: f64, max_iter: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError>
+where F : Fn(&na::DVector<f64>) -> na::DVector<f64>
+{
+    newton_system(&f, |x: &na::DVector<f64>, f_x: &na::DVector<f64>| finite_differences::jacobian_num(&f, x, f_x, dx_num), x0.len() as u32, x0, tol, max_iter)
+}
+
+/// Rank-one update of the Jacobian used by Broyden's method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BroydenUpdate {
+    Good,// Updates the Jacobian with the smallest change (in Frobenius norm) satisfying the secant equation
+    Bad, // Updates the inverse Jacobian with the smallest change (in Frobenius norm) satisfying the secant equation
+}
+
+/// Broyden's quasi-Newton method for solving a system of non-linear equations F(x) = 0
+/// @param f function to solve
+/// @param x0 initial guess
+/// @param update rank-one update of the Jacobian (good or bad Broyden)
+/// @param tol tolerance on the norm of F and on the norm of the step
+/// @param dx_num numerical differentiation step size, used for the initial Jacobian
+/// @param max_iter maximum number of iterations
+/// @return solution report, where f_x is the norm of F at the solution
+/// @note The initial Jacobian is computed with forward finite-differences. The inverse Jacobian is then updated with rank-one updates, so that each iteration only requires one evaluation of F.
+/// @note No line search is performed : the method converges only from a starting point close enough to the solution.
+/// @note If the step becomes smaller than tol while ||F|| is not, NoProgress is returned.
+pub fn broyden_solve<F>(f: F, x0: &na::DVector<f64>, update: BroydenUpdate, tol: f64, dx_num: f64, max_iter: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError>
+where F : Fn(&na::DVector<f64>) -> na::DVector<f64>
+{
+    let mut x: na::DVector<f64> = x0.clone();
+    let mut f_x: na::DVector<f64> = f(&x);
+    let mut n_fev: u32 = 1;
+    if f_x.len() != x.len() {
+        return Err(SolverError::InvalidInput("F must have as many components as x"));
+    }
+    if !is_finite(&f_x) {
+        return Err(SolverError::NonFiniteValue);
+    }
+
+    // Inverse of the initial Jacobian
+    let jac: na::DMatrix<f64> = finite_differences::jacobian_num(&f, &x, &f_x, dx_num);
+    n_fev += x.len() as u32;
+    let mut jac_inv: na::DMatrix<f64> = match jac.try_inverse() {
+        Some(jac_inv) => jac_inv,
+        None => return Err(SolverError::SingularJacobian),
+    };
+
+    for iter in 0..max_iter {
+        if f_x.norm() < tol {
+            return Ok(SolverResult::new(x, f_x.norm(), iter, n_fev, TerminationReason::FunctionTolerance));
+        }
+
+        // Quasi-Newton step
+        let dx: na::DVector<f64> = -&jac_inv*&f_x;
+        x += &dx;
+        let f_x_new: na::DVector<f64> = f(&x);
+        n_fev += 1;
+        if !is_finite(&f_x_new) {
+            return Err(SolverError::NonFiniteValue);
+        }
+        let df: na::DVector<f64> = &f_x_new - &f_x;
+        f_x = f_x_new;
+
+        if dx.norm() < tol {
+            // A vanishing step is only a success if F itself is close to zero, otherwise x is stuck away from any root
+            if f_x.norm() < tol {
+                return Ok(SolverResult::new(x, f_x.norm(), iter + 1, n_fev, TerminationReason::FunctionTolerance));
+            }
+            return Err(SolverError::NoProgress);
+        }
+
+        // Rank-one update of the inverse Jacobian (Sherman-Morrison formula for the good Broyden update)
+        let jac_inv_df: na::DVector<f64> = &jac_inv*&df;
+        match update {
+            BroydenUpdate::Good => {
+                let dx_t_jac_inv: na::RowDVector<f64> = dx.transpose()*&jac_inv;
+                let denom: f64 = dx.dot(&jac_inv_df);
+                if denom == 0.0 {
+                    return Err(SolverError::SingularJacobian);
+                }
+                jac_inv += (&dx - &jac_inv_df)*dx_t_jac_inv / denom;
+            },
+            BroydenUpdate::Bad => {
+                let denom: f64 = df.norm_squared();
+                if denom == 0.0 {
+                    return Err(SolverError::SingularJacobian);
+                }
+                jac_inv += (&dx - &jac_inv_df)*df.transpose() / denom;
+            },
+        }
+    }
+    Err(SolverError::MaxIterations)
+}
+
+/// Computes the norms of the columns of a matrix, replacing zero norms by one.
+/// @param m matrix
+/// @return vector of the norms of the columns of m
+fn column_norms(m: &na::DMatrix<f64>) -> na::DVector<f64> {
+    na::DVector::from_fn(m.ncols(), |j, _| {
+        let norm: f64 = m.column(j).norm();
+        if norm == 0.0 { 1.0 } else { norm }
+    })
+}
+
+/// Computes the dogleg step of the trust-region subproblem min ||F + J*p|| subject to ||p|| <= delta.
+/// @param jac Jacobian matrix (or its approximation) at the current point
+/// @param f_x value of F at the current point
+/// @param delta radius of the trust region
+/// @return the dogleg step
+fn dogleg_step(jac: &na::DMatrix<f64>, f_x: &na::DVector<f64>, delta: f64) -> na::DVector<f64> {
+    // Gauss-Newton step, taken whole if it lies inside the trust region
+    let p_gn: Option<na::DVector<f64>> = jac.clone().lu().solve(&(-f_x)).filter(is_finite);
+    if let Some(p) = &p_gn {
+        if p.norm() <= delta {
+            return p.clone();
+        }
+    }
+
+    // Minimizer of the quadratic model along the steepest descent direction of 0.5*||F||^2 (Cauchy point)
+    let grad: na::DVector<f64> = jac.transpose()*f_x;
+    let grad_norm: f64 = grad.norm();
+    let jac_grad_norm: f64 = (jac*&grad).norm();
+    if grad_norm == 0.0 {
+        return na::DVector::zeros(f_x.len());
+    }
+    let p_sd: na::DVector<f64> = if jac_grad_norm > 0.0 { -(grad_norm/jac_grad_norm).powi(2)*&grad } else { -delta/grad_norm*&grad };
+    let p_sd_norm: f64 = p_sd.norm();
+    if p_sd_norm >= delta || p_gn.is_none() {
+        return -delta/grad_norm*&grad;
+    }
+
+    // Dogleg path : p = p_sd + tau*(p_gn - p_sd), with tau chosen so that ||p|| = delta
+    let p_gn: na::DVector<f64> = p_gn.unwrap();
+    let d: na::DVector<f64> = &p_gn - &p_sd;
+    let a: f64 = d.norm_squared();
+    let b: f64 = 2.0*p_sd.dot(&d);
+    let c: f64 = p_sd_norm.powi(2) - delta.powi(2);
+    let tau: f64 = (-b + f64::sqrt(b*b - 4.0*a*c)) / (2.0*a);
+    p_sd + tau*d
+}
+
+/// Powell's hybrid method (trust-region dogleg with Broyden updates), shared by powell_hybrid_solve and powell_hybrid_solve_num.
+/// @param f function to solve
+/// @param jac Jacobian matrix of f, given x and F(x)
+/// @param jac_fev number of evaluations of f made by each call to jac
+/// @param x0 initial guess
+/// @param tol tolerance on the norm of F and on the radius of the trust region
+/// @param max_iter maximum number of iterations
+/// @return solution report, where f_x is the norm of F at the solution
+fn powell_hybrid<F, J>(f: F, jac: J, jac_fev: u32, x0: &na::DVector<f64>, tol: f64, max_iter: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError>
+where F : Fn(&na::DVector<f64>) -> na::DVector<f64>, J : Fn(&na::DVector<f64>, &na::DVector<f64>) -> na::DMatrix<f64>
+{
+    // Parameters
+    let rho_accept: f64 = 1e-4;// Minimum ratio between the actual and predicted reductions to accept a step
+    let n_refresh: u32 = 10;   // Number of Broyden updates after which the Jacobian is recomputed
+
+    let mut x: na::DVector<f64> = x0.clone();
+    let mut f_x: na::DVector<f64> = f(&x);
+    let mut n_fev: u32 = 1;
+    if f_x.len() != x.len() {
+        return Err(SolverError::InvalidInput("F must have as many components as x"));
+    }
+    if !is_finite(&f_x) {
+        return Err(SolverError::NonFiniteValue);
+    }
+    let mut jac_x: na::DMatrix<f64> = jac(&x, &f_x);
+    n_fev += jac_fev;
+
+    // The trust region is defined as ||D*p|| <= delta, where the scaling factors D are the norms of the columns of the Jacobian
+    let mut scaling: na::DVector<f64> = column_norms(&jac_x);
+    let mut delta: f64 = 100.0*scaling.component_mul(&x).norm();// Radius of the trust region
+    if delta == 0.0 { delta = 100.0; }
+    let mut n_success: u32 = 0;// Number of consecutive accepted steps
+    let mut n_fail: u32 = 0;   // Number of consecutive rejected steps
+    let mut n_updates: u32 = 0;// Number of Broyden updates since the last evaluation of the Jacobian
+
+    for iter in 0..max_iter {
+        let f_norm: f64 = f_x.norm();
+        if f_norm < tol {
+            return Ok(SolverResult::new(x, f_norm, iter, n_fev, TerminationReason::FunctionTolerance));
+        }
+        if delta < tol*(scaling.component_mul(&x).norm() + tol) {
+            // The trust region collapsed while ||F|| is still above the tolerance : x is stuck away from any root
+            return Err(SolverError::NoProgress);
+        }
+
+        // Trial step, computed in the scaled variables q = D*p
+        let mut jac_scaled: na::DMatrix<f64> = jac_x.clone();
+        for j in 0..x.len() {
+            jac_scaled.column_mut(j).scale_mut(1.0/scaling[j]);
+        }
+        let p: na::DVector<f64> = dogleg_step(&jac_scaled, &f_x, delta).component_div(&scaling);
+        let p_norm: f64 = scaling.component_mul(&p).norm();
+        if iter == 0 {
+            delta = f64::min(delta, p_norm);
+        }
+        let x_new: na::DVector<f64> = &x + &p;
+        let f_x_new: na::DVector<f64> = f(&x_new);
+        n_fev += 1;
+        let f_norm_new: f64 = if is_finite(&f_x_new) { f_x_new.norm() } else { f64::INFINITY };
+
+        // Ratio between the actual and the predicted reductions of ||F||^2
+        let predicted: f64 = f_norm.powi(2) - (&f_x + &jac_x*&p).norm_squared();
+        let actual: f64 = f_norm.powi(2) - f_norm_new.powi(2);
+        let rho: f64 = if predicted > 0.0 { actual/predicted } else { -1.0 };
+
+        // Update the radius of the trust region
+        if rho < 0.1 {
+            delta *= 0.5;
+            n_success = 0;
+            n_fail += 1;
+        } else {
+            n_fail = 0;
+            n_success += 1;
+            if rho >= 0.5 || n_success > 1 {
+                delta = f64::max(delta, 2.0*p_norm);
+            }
+            if f64::abs(rho - 1.0) <= 0.1 {
+                delta = 2.0*p_norm;
+            }
+        }
+
+        if f_norm_new.is_finite() {
+            // Broyden update of the Jacobian (good Broyden), also made on rejected steps since they carry information
+            if p_norm > 0.0 {
+                let df: na::DVector<f64> = &f_x_new - &f_x;
+                jac_x += (&df - &jac_x*&p)*p.transpose() / p.norm_squared();
+                n_updates += 1;
+            }
+            if rho >= rho_accept {
+                x = x_new;
+                f_x = f_x_new;
+            }
+        }
+
+        // Recompute the Jacobian when the Broyden approximation is too old or fails to make progress
+        if n_fail >= 2 || n_updates >= n_refresh {
+            jac_x = jac(&x, &f_x);
+            n_fev += jac_fev;
+            scaling = scaling.zip_map(&column_norms(&jac_x), f64::max);
+            n_fail = 0;
+            n_updates = 0;
+        }
+    }
+    Err(SolverError::MaxIterations)
+}
+
+/// Powell's hybrid method for solving a system of non-linear equations F(x) = 0
+/// @param f function to solve
+/// @param jac Jacobian matrix of f, with jac(x)[(i, j)] = dF_i/dx_j
+/// @param x0 initial guess
+/// @param tol tolerance on the norm of F and on the radius of the trust region
+/// @param max_iter maximum number of iterations
+/// @return solution report, where f_x is the norm of F at the solution
+/// @note This method is similar to MINPACK's hybrj : trust-region dogleg steps combine the Gauss-Newton and steepest descent directions of ||F||^2, and the Jacobian is updated with Broyden's rank-one updates between periodic evaluations.
+/// @note It is more robust than newton_system_solve on badly conditioned systems.
+/// @note If the trust region collapses while ||F|| is not below tol, NoProgress is returned.
+pub fn powell_hybrid_solve<F, J>(f: F, jac: J, x0: &na::DVector<f64>, tol: f64, max_iter: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError>
+where F : Fn(&na::DVector<f64>) -> na::DVector<f64>, J : Fn(&na::DVector<f64>) -> na::DMatrix<f64>
+{
+    powell_hybrid(f, |x: &na::DVector<f64>, _f_x: &na::DVector<f64>| jac(x), 0, x0, tol, max_iter)
+}
+
+/// Powell's hybrid method for solving a system of non-linear equations F(x) = 0
+/// @param f function to solve
+/// @param x0 initial guess
+/// @param tol tolerance on the norm of F and on the radius of the trust region
+/// @param dx_num numerical differentiation step size
+/// @param max_iter maximum number of iterations
+/// @return solution report, where f_x is the norm of F at the solution
+/// @note This method is similar to MINPACK's hybrd : trust-region dogleg steps combine the Gauss-Newton and steepest descent directions of ||F||^2, and the Jacobian is updated with Broyden's rank-one updates between periodic evaluations.
+/// @note If the trust region collapses while ||F|| is not below tol, NoProgress is returned.
+/// @note This method uses forward finite-differences to compute the Jacobian matrix.
+pub fn powell_hybrid_solve_num<F>(f: F, x0: &na::DVector<f64>, tol: f64, dx_num: f64, max_iter: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError>
+where F : Fn(&na::DVector<f64>) -> na::DVector<f64>
+{
+    powell_hybrid(&f, |x: &na::DVector<f64>, f_x: &na::DVector<f64>| finite_differences::jacobian_num(&f, x, f_x, dx_num), x0.len() as u32, x0, tol, max_iter)
+}
@@ -1,5 +1,8 @@
 extern crate nalgebra as na;
 
+use crate::solver_error::SolverError;
+use crate::solver_result::{SolverResult, TerminationReason};
+
 // let mut tuple_list2: Vec<(u16, u16)> = vec![(1, 5), (0, 17), (8, 2)];
 // tuple_list2.sort_by(|a, b| a.1.cmp(&b.1));
 
@@ -45,9 +48,18 @@ fn compute_simplex_size(simplex: &Vec<(na::DVector<f64>, f64)>) -> f64 {
 /// @param f  The function to optimize.
 /// @param x0 The starting point of the algorithm.
 /// @param simplex_size The maximum size of the simplex at initialization.
-pub fn nelder_mead_minimize<F>(f: F, x0: &na::DVector<f64>, simplex_size: f64, tol: f64, max_iter: u32, verbose: bool) -> (na::DVector<f64>, f64)
+/// @return The solution report, holding the best point of the simplex and its function value.
+/// @note The objective function may return +inf as a penalty, but never NaN.
+pub fn nelder_mead_minimize<F>(f: F, x0: &na::DVector<f64>, simplex_size: f64, tol: f64, max_iter: u32, verbose: bool) -> Result<SolverResult<na::DVector<f64>>, SolverError>
 where F : Fn(&na::DVector<f64>) -> f64
 {
+    if x0.is_empty() {
+        return Err(SolverError::InvalidInput("the starting point must have at least one dimension"));
+    }
+    if simplex_size <= 0.0 {
+        return Err(SolverError::InvalidInput("the simplex size must be strictly positive"));
+    }
+
     // Parameters
     let alpha: f64 = 1.0; // Reflection coefficient
     let gamma: f64 = 2.0; // Expansion coefficient
@@ -62,6 +74,7 @@ where F : Fn(&na::DVector<f64>) -> f64
         simplex[i+1].0[i] += simplex_size;  // Initialise each point in the simplex to be simplex_size away from x0 along aech direction
         simplex[i+1].1 = f(&simplex[i+1].0);// Evaluate objective function at each point in the simplex
     }
+    let mut n_fev: u32 = simplex.len() as u32;
 
     let N: &usize = &simplex.len(); // Number of points in the simplex
 
@@ -73,6 +86,10 @@ where F : Fn(&na::DVector<f64>) -> f64
     }
 
     for iter in 0..max_iter {
+        if simplex.iter().any(|p| p.1.is_nan()) {
+            return Err(SolverError::NonFiniteValue);
+        }
+
         // Sort the simplex by function value
         simplex.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
 
@@ -89,7 +106,7 @@ where F : Fn(&na::DVector<f64>) -> f64
             if verbose {
                 println!("Converged on function values after {} iterations", iter);
             }
-            return simplex[0].clone();
+            return Ok(SolverResult::new(simplex[0].0.clone(), simplex[0].1, iter, n_fev, TerminationReason::FunctionTolerance));
         }
 
         // Termination condition 2 : convergence of the simplex size
@@ -98,7 +115,7 @@ where F : Fn(&na::DVector<f64>) -> f64
             if verbose {
                 println!("Converged on simplex size after {} iterations", iter);
             }
-            return simplex[0].clone();
+            return Ok(SolverResult::new(simplex[0].0.clone(), simplex[0].1, iter, n_fev, TerminationReason::StepTolerance));
         }
 
         // Compute the centroid of the simplex (excluding the worst point)
@@ -115,6 +132,7 @@ where F : Fn(&na::DVector<f64>) -> f64
         // Compute the reflection point : xr = x0 + alpha*(x0 - x[N+1])
         let reflection: na::DVector<f64> = &centroid + alpha*(&centroid - &simplex[N-1].0);
         let f_reflection: f64 = f(&reflection);
+        n_fev += 1;
 
         if verbose {
             println!("Reflection: {} -> {}", reflection, f_reflection);
@@ -130,6 +148,7 @@ where F : Fn(&na::DVector<f64>) -> f64
         if &f_reflection < &simplex[0].1 {
             let expansion: na::DVector<f64> = &centroid + gamma*(&reflection - &centroid);
             let f_expansion: f64 = f(&expansion);
+            n_fev += 1;
 
             if verbose {
                 println!("Expansion: {} -> {}", expansion, f_expansion);
@@ -147,6 +166,7 @@ where F : Fn(&na::DVector<f64>) -> f64
         if &f_reflection >= &simplex[N-2].1 {
             let contraction: na::DVector<f64> = &centroid + rho*(&simplex[N-1].0 - &centroid);
             let f_contraction: f64 = f(&contraction);
+            n_fev += 1;
 
             if verbose {
                 println!("Contraction: {} -> {}", contraction, f_contraction);
@@ -161,6 +181,7 @@ where F : Fn(&na::DVector<f64>) -> f64
                     simplex[i].0 = &simplex[0].0 + sigma*(&simplex[i].0 - &simplex[0].0);
                     simplex[i].1 = f(&simplex[i].0);
                 }
+                n_fev += (N - 1) as u32;
 
                 if verbose {
                     println!("Shrinking the whole simplex");
@@ -173,5 +194,9 @@ where F : Fn(&na::DVector<f64>) -> f64
         println!("Maximum number of iterations reached");
     }
 
-    return simplex[0].clone();
+    if simplex.iter().any(|p| p.1.is_nan()) {
+        return Err(SolverError::NonFiniteValue);
+    }
+    simplex.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+    Ok(SolverResult::new(simplex[0].0.clone(), simplex[0].1, max_iter, n_fev, TerminationReason::MaxIterations))
 }
\ No newline at end of file
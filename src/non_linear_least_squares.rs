@@ -1,36 +1,42 @@
 extern crate nalgebra as na;
 
+use crate::finite_differences;
+use crate::solver_error::SolverError;
+use crate::solver_result::{SolverResult, TerminationReason};
+
 /// Gauss-Newton algorithm to solve a non-linear least squares problem. It minimizes the difference between fct_lsqr(xp, beta) and the data (xp, yp)
 /// @param xp: vector of x values of the data points
 /// @param yp: vector of y values of the data points
 /// @param fct_lsqr: function that computes the least squares function. It takes as input the parameters and the data points and returns the model for the data fit : .
-pub fn gauss_newton_lsqr<F: Fn(&na::DVector<f64>, &na::DVector<f64>) -> na::DVector<f64>>(xp: &na::DVector<f64>, yp: &na::DVector<f64>, fct_lsqr: &F, beta0: &na::DVector<f64>, tol: f64, n_iter_max: u32, dx_num: f64, verbose: bool) -> na::DVector<f64> {
+/// @return solution report, where f_x is the sum of the squared residuals at the solution
+pub fn gauss_newton_lsqr<F: Fn(&na::DVector<f64>, &na::DVector<f64>) -> na::DVector<f64>>(xp: &na::DVector<f64>, yp: &na::DVector<f64>, fct_lsqr: &F, beta0: &na::DVector<f64>, tol: f64, n_iter_max: u32, dx_num: f64, verbose: bool) -> Result<SolverResult<na::DVector<f64>>, SolverError> {
+    if xp.len() != yp.len() {
+        return Err(SolverError::InvalidInput("xp and yp must have the same length"));
+    }
     let mut beta: na::DVector<f64> = beta0.clone();
 
-    let n_pts:  usize = xp.len();
     let n_dims: usize = beta.len();
+    let mut n_fev: u32 = 0;
 
     for iter in 0..n_iter_max {
-        let residuals = yp - fct_lsqr(xp, &beta);// Residual vector
-
-        // Compute the Jacobian
-        let mut jac: na::DMatrix<f64> = na::DMatrix::zeros(n_pts, n_dims);
-
         let f_beta: na::DVector<f64> = fct_lsqr(&xp, &beta);
-        for j in 0..n_dims {
-            let mut beta_dx: na::DVector<f64> = beta.clone();
-            beta_dx[j] += dx_num;
-            let jac_col = (fct_lsqr(&xp, &beta_dx) - &f_beta) / dx_num;
-            for i in 0..n_pts {
-                jac[(i, j)] = jac_col[i];
-            }
+        let residuals = yp - &f_beta;// Residual vector
+        n_fev += 1 + n_dims as u32;
+        if residuals.iter().any(|r| !r.is_finite()) {
+            return Err(SolverError::NonFiniteValue);
         }
 
+        // Compute the Jacobian
+        let jac: na::DMatrix<f64> = finite_differences::jacobian_num(|b: &na::DVector<f64>| fct_lsqr(xp, b), &beta, &f_beta, dx_num);
+
         // Compute the Gauss-Newton step
         let jac_t = jac.transpose();// J^T
         let jac_t_jac = &jac_t*jac;// J^T*J
         let jac_t_res = - &jac_t*&residuals;// J^T*residuals
-        let delta_beta = jac_t_jac.qr().solve(&jac_t_res).unwrap();// (J^T*J)^{-1}*J^T*residuals
+        let delta_beta = match jac_t_jac.qr().solve(&jac_t_res) {// (J^T*J)^{-1}*J^T*residuals
+            Some(delta_beta) => delta_beta,
+            None => return Err(SolverError::SingularJacobian),
+        };
         
         if verbose {
             println!("iter = {}\tbeta = {}\tresiduals = {}\tdelta_beta = {}", iter, &beta, &residuals, &delta_beta);
@@ -39,9 +45,131 @@ pub fn gauss_newton_lsqr<F: Fn(&na::DVector<f64>, &na::DVector<f64>) -> na::DVec
         beta = &beta - &delta_beta;
 
         if delta_beta.norm() < tol {
-            break;
+            n_fev += 1;
+            return Ok(SolverResult::new(beta.clone(), (yp - fct_lsqr(xp, &beta)).norm_squared(), iter + 1, n_fev, TerminationReason::StepTolerance));
+        }
+    }
+
+    n_fev += 1;
+    Ok(SolverResult::new(beta.clone(), (yp - fct_lsqr(xp, &beta)).norm_squared(), n_iter_max, n_fev, TerminationReason::MaxIterations))
+}
+
+/// Levenberg-Marquardt algorithm to solve a non-linear least squares problem. It minimizes the difference between fct_lsqr(xp, beta) and the data (xp, yp)
+/// @param xp: vector of x values of the data points
+/// @param yp: vector of y values of the data points
+/// @param fct_lsqr: function that computes the least squares function. It takes as input the parameters and the data points and returns the model for the data fit : .
+/// @param beta0: initial guess of the parameters
+/// @param tol: tolerance on the scaled gradient, on the step size and on the relative reduction of the sum of the squared residuals
+/// @param n_iter_max: maximum number of iterations
+/// @param dx_num: numerical differentiation step size
+/// @return solution report, where f_x is the sum of the squared residuals at the solution
+/// @note The damping parameter is adapted with Nielsen's update rule, and the damping term is scaled by the diagonal of J^T*J (Marquardt's scaling).
+/// @note Unlike gauss_newton_lsqr, this method converges from poor initial guesses and handles a singular J^T*J.
+#[allow(clippy::too_many_arguments)]
+pub fn levenberg_marquardt_lsqr<F: Fn(&na::DVector<f64>, &na::DVector<f64>) -> na::DVector<f64>>(xp: &na::DVector<f64>, yp: &na::DVector<f64>, fct_lsqr: &F, beta0: &na::DVector<f64>, tol: f64, n_iter_max: u32, dx_num: f64, verbose: bool) -> Result<SolverResult<na::DVector<f64>>, SolverError> {
+    if xp.len() != yp.len() {
+        return Err(SolverError::InvalidInput("xp and yp must have the same length"));
+    }
+    // Parameters
+    let tau: f64 = 1e-3;       // Initial damping, relative to the largest diagonal element of J^T*J
+    let d_min: f64 = 1e-12;    // Lower bound of the scaling factors, relative to the largest one
+
+    let mut beta: na::DVector<f64> = beta0.clone();
+    let n_dims: usize = beta.len();
+
+    let mut f_beta: na::DVector<f64> = fct_lsqr(xp, &beta);
+    let mut residuals: na::DVector<f64> = &f_beta - yp;// Residual vector
+    let mut n_fev: u32 = 1;
+    if residuals.iter().any(|r| !r.is_finite()) {
+        return Err(SolverError::NonFiniteValue);
+    }
+    let mut sum_sq: f64 = residuals.norm_squared();
+
+    let mut jac: na::DMatrix<f64> = finite_differences::jacobian_num(|b: &na::DVector<f64>| fct_lsqr(xp, b), &beta, &f_beta, dx_num);
+    n_fev += n_dims as u32;
+    let mut jac_t_jac: na::DMatrix<f64> = jac.transpose()*&jac;// J^T*J
+    let mut grad: na::DVector<f64> = jac.transpose()*&residuals;// J^T*residuals (half the gradient of the sum of squares)
+
+    let mut mu: f64 = tau*jac_t_jac.diagonal().max();
+    let mut nu: f64 = 2.0;
+
+    for iter in 0..n_iter_max {
+        // Termination condition 1 : gradient, measured as the cosine of the angle between the residuals and the columns of the Jacobian (as in MINPACK)
+        let res_norm: f64 = residuals.norm();
+        let cos_max: f64 = (0..n_dims).map(|j| {
+            let col_norm: f64 = jac.column(j).norm();
+            if col_norm == 0.0 || res_norm == 0.0 { 0.0 } else { f64::abs(grad[j]) / (col_norm*res_norm) }
+        }).fold(0.0, f64::max);
+        if cos_max < tol {
+            return Ok(SolverResult::new(beta, sum_sq, iter, n_fev, TerminationReason::GradientTolerance));
+        }
+
+        // Solve (J^T*J + mu*D)*delta_beta = -J^T*residuals, with D the diagonal of J^T*J
+        let diag: na::DVector<f64> = jac_t_jac.diagonal();
+        let diag_min: f64 = d_min*f64::max(diag.max(), 1.0);
+        let scaling: na::DVector<f64> = diag.map(|d| f64::max(d, diag_min));
+        let mut lhs: na::DMatrix<f64> = jac_t_jac.clone();
+        for j in 0..n_dims {
+            lhs[(j, j)] += mu*scaling[j];
+        }
+        let delta_beta: na::DVector<f64> = match lhs.cholesky() {
+            Some(chol) => chol.solve(&(-&grad)),
+            None => {
+                // Damping too small for the system to be positive definite
+                mu *= nu;
+                nu *= 2.0;
+                if !mu.is_finite() {
+                    return Err(SolverError::SingularJacobian);
+                }
+                continue;
+            },
+        };
+
+        // Termination condition 2 : step size
+        if delta_beta.norm() < tol*(beta.norm() + tol) {
+            return Ok(SolverResult::new(beta, sum_sq, iter, n_fev, TerminationReason::StepTolerance));
+        }
+
+        // Evaluate the trial point
+        let beta_new: na::DVector<f64> = &beta + &delta_beta;
+        let f_beta_new: na::DVector<f64> = fct_lsqr(xp, &beta_new);
+        let residuals_new: na::DVector<f64> = &f_beta_new - yp;
+        n_fev += 1;
+        let sum_sq_new: f64 = if residuals_new.iter().all(|r| r.is_finite()) { residuals_new.norm_squared() } else { f64::INFINITY };
+
+        // Gain ratio between the actual and the predicted reduction of the sum of squares
+        let predicted: f64 = delta_beta.dot(&(mu*scaling.component_mul(&delta_beta) - &grad));
+        let rho: f64 = (sum_sq - sum_sq_new) / predicted;
+
+        if verbose {
+            println!("iter = {}\tbeta = {}\tsum_sq = {}\tmu = {}\trho = {}", iter, &beta, sum_sq, mu, rho);
+        }
+
+        if rho > 0.0 {
+            // Accept the step and decrease the damping
+            let reduction: f64 = (sum_sq - sum_sq_new) / sum_sq;
+            beta = beta_new;
+            f_beta = f_beta_new;
+            residuals = residuals_new;
+            sum_sq = sum_sq_new;
+            mu *= f64::max(1.0/3.0, 1.0 - (2.0*rho - 1.0).powi(3));
+            nu = 2.0;
+
+            // Termination condition 3 : relative reduction of the sum of squares
+            if reduction < tol || sum_sq == 0.0 {
+                return Ok(SolverResult::new(beta, sum_sq, iter + 1, n_fev, TerminationReason::FunctionTolerance));
+            }
+
+            jac = finite_differences::jacobian_num(|b: &na::DVector<f64>| fct_lsqr(xp, b), &beta, &f_beta, dx_num);
+            n_fev += n_dims as u32;
+            jac_t_jac = jac.transpose()*&jac;
+            grad = jac.transpose()*&residuals;
+        } else {
+            // Reject the step and increase the damping
+            mu *= nu;
+            nu *= 2.0;
         }
     }
 
-    return beta;
+    Ok(SolverResult::new(beta, sum_sq, n_iter_max, n_fev, TerminationReason::MaxIterations))
 }
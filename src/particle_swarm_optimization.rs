@@ -1,40 +1,147 @@
 extern crate nalgebra as na;
 
-#[path = "./xorwow.rs"]
-mod xorwow;
-
-use xorwow::Xorwow;
+use crate::xorwow::Xorwow;
+use crate::solver_error::SolverError;
+use crate::solver_result::{SolverResult, TerminationReason};
 
 /// A particle in the particle swarm optimization algorithm
 struct Particle {
-    x: na::DVector<f64>,// position
-    v: na::DVector<f64>,// velocity
-    fx: f64,            // function value at x
+    x: na::DVector<f64>,     // position
+    v: na::DVector<f64>,     // velocity
+    fx: f64,                 // function value at x
+    x_best: na::DVector<f64>,// best position visited by the particle
+    fx_best: f64,            // function value at x_best
 }
 
 impl Particle {
     fn new(x: na::DVector<f64>, v: na::DVector<f64>, fx: f64) -> Particle {
         Particle {
-            x: x,
-            v: v,
-            fx: fx,
+            x_best: x.clone(),
+            fx_best: fx,
+            x,
+            v,
+            fx,
         }
     }
 }
 
-pub fn particle_swarm_minimize<F: Fn(&na::DVector<f64>) -> f64>(f: F, n_particles: u32, lb: &na::DVector<f64>, ub: &na::DVector<f64>, tol: f64, n_iter_max: u32, rng_seed: u32) -> f64 {
-    let mut particles: Vec<Particle> = Vec::new();
+/// Computes the standard deviation of the best function values of the particles.
+/// @param particles The particles.
+/// @return The standard deviation of the best function values.
+fn standard_deviation_of_best_values(particles: &Vec<Particle>) -> f64 {
+    let mean: f64 = particles.iter().map(|p| p.fx_best).sum::<f64>() / particles.len() as f64;
+    let mut sum: f64 = 0.0;
+    for p in particles {
+        sum += (p.fx_best - mean).powi(2);
+    }
+    f64::sqrt(sum / particles.len() as f64)
+}
 
+/// Particle Swarm Optimization (PSO) global minimizer.
+/// @param f The function to minimize.
+/// @param n_particles Number of particles in the swarm.
+/// @param lb Lower bounds of the search space.
+/// @param ub Upper bounds of the search space.
+/// @param tol Tolerance on the spread of the best function values of the particles and on the size of the swarm.
+/// @param n_iter_max Maximum number of iterations.
+/// @param rng_seed Seed of the random number generator. The optimization is deterministic for a given seed.
+/// @return The solution report, holding the best position found by the swarm and its function value.
+/// @note Particles are kept inside the bounds [lb, ub] : a particle hitting a bound is stopped on it along that direction.
+pub fn particle_swarm_minimize<F: Fn(&na::DVector<f64>) -> f64>(f: F, n_particles: u32, lb: &na::DVector<f64>, ub: &na::DVector<f64>, tol: f64, n_iter_max: u32, rng_seed: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError> {
+    // Parameters (constriction coefficients of Clerc & Kennedy)
+    let w:  f64 = 0.7298;  // Inertia coefficient
+    let c1: f64 = 1.49618; // Cognitive coefficient (attraction towards the best position of the particle)
+    let c2: f64 = 1.49618; // Social coefficient (attraction towards the best position of the swarm)
+    let v_max_ratio: f64 = 0.5;// Maximum velocity, as a fraction of the size of the search space
+
+    if lb.len() != ub.len() || lb.is_empty() {
+        return Err(SolverError::InvalidInput("lb and ub must have the same non-zero length"));
+    }
+    if lb.iter().zip(ub.iter()).any(|(l, u)| l > u) {
+        return Err(SolverError::InvalidInput("lb must be lower than or equal to ub"));
+    }
+    if n_particles == 0 {
+        return Err(SolverError::InvalidInput("the swarm must contain at least one particle"));
+    }
+
+    let n_dims: usize = lb.len();
+    let v_max: na::DVector<f64> = v_max_ratio * (ub - lb);
     let mut rng = Xorwow::new(rng_seed);
+    let mut n_fev: u32 = 0;
 
+    // Initialise the particles uniformly in the search space
+    let mut particles: Vec<Particle> = Vec::new();
     for _ in 0..n_particles {
-        let x = lb + (ub - lb) * rng.rand_vec(lb.len());
-        let v = (lb - ub) * rng.rand_vec(lb.len());
+        let x = lb + (ub - lb).component_mul(&rng.rand_vec(n_dims));
+        let v = (ub - lb).component_mul(&(2.0*rng.rand_vec(n_dims) - na::DVector::from_element(n_dims, 1.0)));
+        let v = v.zip_map(&v_max, |vi, vm| vi.clamp(-vm, vm));
         let fx = f(&x);
-        println!("x = {}\tv = {}\tf(x) = {}", &x, &v, fx);// DEBUG
+        n_fev += 1;
+        if fx.is_nan() {
+            return Err(SolverError::NonFiniteValue);
+        }
         particles.push(Particle::new(x, v, fx));
     }
 
-    let x = (lb + ub) / 2.0;
-    return f(&x);
-}
\ No newline at end of file
+    // Best position of the swarm
+    let mut i_best: usize = 0;
+    for i in 1..particles.len() {
+        if particles[i].fx_best < particles[i_best].fx_best { i_best = i; }
+    }
+    let mut x_best: na::DVector<f64> = particles[i_best].x_best.clone();
+    let mut fx_best: f64 = particles[i_best].fx_best;
+
+    for iter in 0..n_iter_max {
+        for p in particles.iter_mut() {
+            // Update the velocity
+            let r1 = rng.rand_vec(n_dims);
+            let r2 = rng.rand_vec(n_dims);
+            p.v = w*&p.v + c1*r1.component_mul(&(&p.x_best - &p.x)) + c2*r2.component_mul(&(&x_best - &p.x));
+            p.v = p.v.zip_map(&v_max, |vi, vm| vi.clamp(-vm, vm));
+
+            // Update the position, keeping the particle inside the bounds
+            p.x += &p.v;
+            for j in 0..n_dims {
+                if p.x[j] < lb[j] {
+                    p.x[j] = lb[j];
+                    p.v[j] = 0.0;
+                } else if p.x[j] > ub[j] {
+                    p.x[j] = ub[j];
+                    p.v[j] = 0.0;
+                }
+            }
+
+            // Update the personal best
+            p.fx = f(&p.x);
+            n_fev += 1;
+            if p.fx.is_nan() {
+                return Err(SolverError::NonFiniteValue);
+            }
+            if p.fx < p.fx_best {
+                p.x_best = p.x.clone();
+                p.fx_best = p.fx;
+            }
+        }
+
+        // Update the global best
+        for p in particles.iter() {
+            if p.fx_best < fx_best {
+                x_best = p.x_best.clone();
+                fx_best = p.fx_best;
+            }
+        }
+
+        // Termination condition 1 : convergence of the best values of the particles
+        if standard_deviation_of_best_values(&particles) < tol {
+            return Ok(SolverResult::new(x_best, fx_best, iter + 1, n_fev, TerminationReason::FunctionTolerance));
+        }
+
+        // Termination condition 2 : collapse of the swarm around the best position
+        let swarm_size: f64 = particles.iter().map(|p| (&p.x - &x_best).norm()).fold(0.0, f64::max);
+        if swarm_size < tol {
+            return Ok(SolverResult::new(x_best, fx_best, iter + 1, n_fev, TerminationReason::StepTolerance));
+        }
+    }
+
+    Ok(SolverResult::new(x_best, fx_best, n_iter_max, n_fev, TerminationReason::MaxIterations))
+}
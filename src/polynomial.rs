@@ -0,0 +1,142 @@
+extern crate nalgebra as na;
+
+use na::Complex;
+
+use crate::solver_error::SolverError;
+use crate::univariate_solvers;
+
+// Polynomials are represented by the vector of their real coefficients in increasing order of power : coeffs[i] multiplies x^i.
+
+/// Evaluates a polynomial at a real point with Horner's scheme.
+/// @param coeffs coefficients of the polynomial, in increasing order of power
+/// @param x point at which the polynomial is evaluated
+/// @return value of the polynomial at x
+pub fn polynomial_eval(coeffs: &[f64], x: f64) -> f64 {
+    coeffs.iter().rev().fold(0.0, |acc, &c| acc*x + c)
+}
+
+/// Evaluates a polynomial at a complex point with Horner's scheme.
+/// @param coeffs coefficients of the polynomial, in increasing order of power
+/// @param z point at which the polynomial is evaluated
+/// @return value of the polynomial at z
+pub fn polynomial_eval_complex(coeffs: &[f64], z: Complex<f64>) -> Complex<f64> {
+    coeffs.iter().rev().fold(Complex::new(0.0, 0.0), |acc, &c| acc*z + c)
+}
+
+/// Computes the derivative of a polynomial.
+/// @param coeffs coefficients of the polynomial, in increasing order of power
+/// @return coefficients of the derivative, in increasing order of power
+pub fn polynomial_derivative(coeffs: &[f64]) -> Vec<f64> {
+    coeffs.iter().enumerate().skip(1).map(|(i, &c)| (i as f64)*c).collect()
+}
+
+/// Divides a polynomial by the factor corresponding to one of its roots (deflation).
+/// @param coeffs coefficients of the polynomial, in increasing order of power
+/// @param root root of the polynomial. A real root removes the factor (x - root), a complex root removes the real factor (x - root)*(x - conj(root)).
+/// @return coefficients of the quotient, in increasing order of power. The remainder, which is zero for an exact root, is discarded.
+pub fn polynomial_deflate(coeffs: &[f64], root: Complex<f64>) -> Vec<f64> {
+    // Divisor x^2 + p*x + q, or x + p for a real root
+    let (p, q): (f64, f64) = if root.im == 0.0 { (-root.re, 0.0) } else { (-2.0*root.re, root.norm_sqr()) };
+    let degree_divisor: usize = if root.im == 0.0 { 1 } else { 2 };
+    let n: usize = coeffs.len();
+    if n <= degree_divisor {
+        return vec![];
+    }
+    // Synthetic division, from the highest power downwards
+    let mut quotient: Vec<f64> = vec![0.0; n - degree_divisor];
+    let mut rem: Vec<f64> = coeffs.to_vec();
+    for k in (0..n - degree_divisor).rev() {
+        let c: f64 = rem[k + degree_divisor];
+        quotient[k] = c;
+        rem[k + degree_divisor - 1] -= p*c;
+        if degree_divisor == 2 {
+            rem[k] -= q*c;
+        }
+    }
+    quotient
+}
+
+/// Polishes a complex root of a polynomial with Newton's method.
+/// @return the polished root, or the initial estimate if the iterations do not converge
+fn polish_complex_root(coeffs: &[f64], dcoeffs: &[f64], z0: Complex<f64>, tol: f64, max_iter: u32) -> Complex<f64> {
+    let mut z: Complex<f64> = z0;
+    for _ in 0..max_iter {
+        let dp: Complex<f64> = polynomial_eval_complex(dcoeffs, z);
+        if dp.norm_sqr() == 0.0 {
+            return z0;
+        }
+        let dz: Complex<f64> = polynomial_eval_complex(coeffs, z)/dp;
+        if !dz.re.is_finite() || !dz.im.is_finite() {
+            return z0;
+        }
+        z -= dz;
+        if dz.norm_sqr().sqrt() < tol {
+            return z;
+        }
+    }
+    z0
+}
+
+/// Computes all the (complex) roots of a polynomial with real coefficients
+/// @param coeffs coefficients of the polynomial, in increasing order of power : coeffs[i] multiplies x^i
+/// @param polish if true, each root is refined with Newton's method on the polynomial (newton_solve for real roots)
+/// @param tol tolerance of the polishing
+/// @param max_iter maximum number of iterations of the polishing
+/// @return the roots, repeated according to their multiplicity, sorted by increasing real part then imaginary part
+/// @note The roots are the eigenvalues of the companion matrix of the polynomial. Roots at zero are removed beforehand by deflation, and complex roots come in conjugate pairs.
+/// @note Multiple roots are only found to about eps^(1/m) relative accuracy, where m is the multiplicity, and their estimates may have spurious imaginary parts of the same order (about 1e-5 for a triple root).
+/// Polishing does not improve them, since Newton's method converges slowly there : roots closer to another root than cluster_radius are left unpolished, and the unpolished estimate is also kept if the polishing fails.
+pub fn polynomial_roots(coeffs: &[f64], polish: bool, tol: f64, max_iter: u32) -> Result<Vec<Complex<f64>>, SolverError> {
+    // Parameters
+    let cluster_radius: f64 = f64::EPSILON.powf(0.2);// Relative distance below which roots are considered as a multiple root (up to multiplicity 5)
+
+    if coeffs.iter().any(|c| !c.is_finite()) {
+        return Err(SolverError::NonFiniteValue);
+    }
+    // Remove the vanishing leading coefficients
+    let degree: usize = match coeffs.iter().rposition(|&c| c != 0.0) {
+        Some(degree) => degree,
+        None => return Err(SolverError::InvalidInput("the polynomial must not be identically zero")),
+    };
+    let poly: &[f64] = &coeffs[..=degree];
+
+    // Roots at zero, removed by deflation
+    let mut roots: Vec<Complex<f64>> = Vec::with_capacity(degree);
+    let mut deflated: Vec<f64> = poly.to_vec();
+    while deflated.len() > 1 && deflated[0] == 0.0 {
+        deflated = polynomial_deflate(&deflated, Complex::new(0.0, 0.0));
+        roots.push(Complex::new(0.0, 0.0));
+    }
+
+    // Eigenvalues of the companion matrix of the monic polynomial
+    let n: usize = deflated.len() - 1;
+    if n > 0 {
+        let mut companion: na::DMatrix<f64> = na::DMatrix::zeros(n, n);
+        for i in 0..n {
+            companion[(0, i)] = -deflated[n - 1 - i]/deflated[n];
+            if i > 0 {
+                companion[(i, i - 1)] = 1.0;
+            }
+        }
+        roots.extend(companion.complex_eigenvalues().iter());
+    }
+
+    if polish {
+        let dpoly: Vec<f64> = polynomial_derivative(poly);
+        let clustered: Vec<bool> = roots.iter().enumerate().map(|(i, z)| {
+            roots.iter().enumerate().any(|(j, w)| i != j && (z - w).norm_sqr().sqrt() < cluster_radius*f64::max(1.0, z.norm_sqr().sqrt()))
+        }).collect();
+        for (z, _) in roots.iter_mut().zip(clustered).filter(|(_, clustered)| !clustered) {
+            if z.im == 0.0 {
+                if let Ok(res) = univariate_solvers::newton_solve(|x: f64| polynomial_eval(poly, x), |x: f64| polynomial_eval(&dpoly, x), z.re, tol, max_iter) {
+                    z.re = res.x;
+                }
+            } else {
+                *z = polish_complex_root(poly, &dpoly, *z, tol, max_iter);
+            }
+        }
+    }
+
+    roots.sort_by(|z1, z2| z1.re.total_cmp(&z2.re).then(z1.im.total_cmp(&z2.im)));
+    Ok(roots)
+}
@@ -0,0 +1,506 @@
+extern crate nalgebra as na;
+
+use std::cell::Cell;
+use std::collections::VecDeque;
+
+use crate::finite_differences;
+use crate::line_search;
+use crate::solver_error::SolverError;
+use crate::solver_result::{SolverResult, TerminationReason};
+
+/// BFGS quasi-Newton method for minimizing a function f(x)
+/// @param f function to minimize
+/// @param grad gradient of f
+/// @param x0 initial guess
+/// @param tol tolerance on the norm of the gradient and on the norm of the step
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note The inverse Hessian approximation is updated only when the curvature condition s^T*y > 0 holds, so that it remains positive definite.
+/// @note Steps are computed with a line search satisfying the strong Wolfe conditions. If the line search fails, the current iterate is returned with termination = LineSearchFailure and converged = false.
+pub fn bfgs_minimize<F, G>(f: F, grad: G, x0: &na::DVector<f64>, tol: f64, max_iter: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError>
+where F : Fn(&na::DVector<f64>) -> f64, G : Fn(&na::DVector<f64>) -> na::DVector<f64>
+{
+    // Parameters
+    let c1: f64 = 1e-4;            // Sufficient decrease coefficient of the line search
+    let c2: f64 = 0.9;             // Curvature coefficient of the line search
+    let max_iter_line_search: u32 = 30;
+
+    let n_dims: usize = x0.len();
+    let identity: na::DMatrix<f64> = na::DMatrix::identity(n_dims, n_dims);
+    let mut x: na::DVector<f64> = x0.clone();
+    let mut f_x: f64 = f(&x);
+    let mut g_x: na::DVector<f64> = grad(&x);
+    let mut n_fev: u32 = 1;
+    if g_x.len() != n_dims {
+        return Err(SolverError::InvalidInput("the gradient must have as many components as x"));
+    }
+    let mut h_inv: na::DMatrix<f64> = identity.clone();// Inverse Hessian approximation
+    let mut first_update: bool = true;
+
+    for iter in 0..max_iter {
+        if !f_x.is_finite() || g_x.iter().any(|gi| !gi.is_finite()) {
+            return Err(SolverError::NonFiniteValue);
+        }
+        if g_x.amax() < tol {
+            return Ok(SolverResult::new(x, f_x, iter, n_fev, TerminationReason::GradientTolerance));
+        }
+
+        // Search direction, reset to steepest descent if the approximation lost positive definiteness
+        let mut p: na::DVector<f64> = -&h_inv*&g_x;
+        if g_x.dot(&p) >= 0.0 {
+            h_inv = identity.clone();
+            first_update = true;
+            p = -&g_x;
+        }
+
+        let point = match line_search::strong_wolfe_line_search(&f, &grad, &x, f_x, &g_x, &p, 1.0, c1, c2, max_iter_line_search, &mut n_fev) {
+            Ok(point) => point,
+            // No acceptable step was found (usually close to a minimum, at the limit of the machine precision) : x is the best point found
+            Err(_) => return Ok(SolverResult::new(x, f_x, iter, n_fev, TerminationReason::LineSearchFailure)),
+        };
+        let s: na::DVector<f64> = point.alpha*&p;
+        let y: na::DVector<f64> = &point.grad - &g_x;
+        x = point.x;
+        f_x = point.f_x;
+        g_x = point.grad;
+
+        if s.norm() < tol {
+            return Ok(SolverResult::new(x, f_x, iter + 1, n_fev, TerminationReason::StepTolerance));
+        }
+
+        // BFGS update of the inverse Hessian, skipped if the curvature condition does not hold
+        let s_t_y: f64 = s.dot(&y);
+        if s_t_y > f64::EPSILON*s.norm()*y.norm() {
+            if first_update {
+                // Scale the initial approximation to the curvature along the first step (Nocedal & Wright, eq. 6.20)
+                h_inv = (s_t_y/y.norm_squared())*&identity;
+                first_update = false;
+            }
+            let rho: f64 = 1.0/s_t_y;
+            let left: na::DMatrix<f64> = &identity - rho*&s*y.transpose();
+            h_inv = &left*&h_inv*left.transpose() + rho*&s*s.transpose();
+        }
+    }
+    Ok(SolverResult::new(x, f_x, max_iter, n_fev, TerminationReason::MaxIterations))
+}
+
+/// BFGS quasi-Newton method for minimizing a function f(x)
+/// @param f function to minimize
+/// @param x0 initial guess
+/// @param tol tolerance on the norm of the gradient and on the norm of the step
+/// @param dx_num numerical differentiation step size
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note This method uses central finite-differences to compute the gradient.
+pub fn bfgs_minimize_num<F>(f: F, x0: &na::DVector<f64>, tol: f64, dx_num: f64, max_iter: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError>
+where F : Fn(&na::DVector<f64>) -> f64
+{
+    let n_grad: Cell<u32> = Cell::new(0);
+    let mut res: SolverResult<na::DVector<f64>> = bfgs_minimize(&f, |x: &na::DVector<f64>| {
+        n_grad.set(n_grad.get() + 1);
+        finite_differences::gradient_num(&f, x, dx_num)
+    }, x0, tol, max_iter)?;
+    res.n_fev += 2*(x0.len() as u32)*n_grad.get();// Two evaluations per dimension for each gradient
+    Ok(res)
+}
+
+/// Limited-memory BFGS (L-BFGS) method for minimizing a function f(x)
+/// @param f function to minimize
+/// @param grad gradient of f
+/// @param x0 initial guess
+/// @param m number of correction pairs (s, y) kept in memory (usually between 3 and 20)
+/// @param tol tolerance on the norm of the gradient and on the norm of the step
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note The product of the inverse Hessian approximation with the gradient is computed with the two-loop recursion (Nocedal & Wright, algorithm 7.4), so that memory and cost per iteration are O(m*n) instead of O(n^2) for BFGS.
+/// @note Steps are computed with the line search of More and Thuente. If the line search fails, the current iterate is returned with termination = LineSearchFailure and converged = false.
+pub fn lbfgs_minimize<F, G>(f: F, grad: G, x0: &na::DVector<f64>, m: usize, tol: f64, max_iter: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError>
+where F : Fn(&na::DVector<f64>) -> f64, G : Fn(&na::DVector<f64>) -> na::DVector<f64>
+{
+    // Parameters
+    let c1: f64 = 1e-4;            // Sufficient decrease coefficient of the line search
+    let c2: f64 = 0.9;             // Curvature coefficient of the line search
+    let max_iter_line_search: u32 = 20;
+
+    if m == 0 {
+        return Err(SolverError::InvalidInput("the history size m must be at least 1"));
+    }
+    let n_dims: usize = x0.len();
+    let mut x: na::DVector<f64> = x0.clone();
+    let mut f_x: f64 = f(&x);
+    let mut g_x: na::DVector<f64> = grad(&x);
+    let mut n_fev: u32 = 1;
+    if g_x.len() != n_dims {
+        return Err(SolverError::InvalidInput("the gradient must have as many components as x"));
+    }
+    let mut history: VecDeque<(na::DVector<f64>, na::DVector<f64>, f64)> = VecDeque::with_capacity(m);// Correction pairs (s, y, 1/(s^T*y)), oldest first
+    let mut alphas: Vec<f64> = vec![0.0; m];
+
+    for iter in 0..max_iter {
+        if !f_x.is_finite() || g_x.iter().any(|gi| !gi.is_finite()) {
+            return Err(SolverError::NonFiniteValue);
+        }
+        if g_x.amax() < tol {
+            return Ok(SolverResult::new(x, f_x, iter, n_fev, TerminationReason::GradientTolerance));
+        }
+
+        // Two-loop recursion : p = -H*g, where H is the L-BFGS approximation of the inverse Hessian
+        let mut q: na::DVector<f64> = g_x.clone();
+        for (i, (s, y, rho)) in history.iter().enumerate().rev() {
+            alphas[i] = rho*s.dot(&q);
+            q.axpy(-alphas[i], y, 1.0);
+        }
+        let gamma: f64 = match history.back() {
+            Some((s, y, _)) => s.dot(y)/y.norm_squared(),// Scaling of the initial approximation (Nocedal & Wright, eq. 7.20)
+            None => 1.0,
+        };
+        q *= gamma;
+        for (i, (s, y, rho)) in history.iter().enumerate() {
+            let beta: f64 = rho*y.dot(&q);
+            q.axpy(alphas[i] - beta, s, 1.0);
+        }
+        let mut p: na::DVector<f64> = -q;
+        if g_x.dot(&p) >= 0.0 {
+            // Reset to steepest descent if the direction is not a descent direction
+            history.clear();
+            p = -&g_x;
+        }
+
+        // The first step is scaled so that its length does not exceed 1
+        let alpha0: f64 = if history.is_empty() { f64::min(1.0, 1.0/p.norm()) } else { 1.0 };
+        let point = match line_search::more_thuente_line_search(&f, &grad, &x, f_x, &g_x, &p, alpha0, 1e10, c1, c2, max_iter_line_search, &mut n_fev) {
+            Ok(point) => point,
+            // No sufficient decrease was found (usually close to a minimum, at the limit of the machine precision) : x is the best point found
+            Err(_) => return Ok(SolverResult::new(x, f_x, iter, n_fev, TerminationReason::LineSearchFailure)),
+        };
+        let s: na::DVector<f64> = point.alpha*&p;
+        let y: na::DVector<f64> = &point.grad - &g_x;
+        x = point.x;
+        f_x = point.f_x;
+        g_x = point.grad;
+
+        if s.norm() < tol {
+            return Ok(SolverResult::new(x, f_x, iter + 1, n_fev, TerminationReason::StepTolerance));
+        }
+
+        // Store the correction pair, dropping the oldest one, unless the curvature condition does not hold
+        let s_t_y: f64 = s.dot(&y);
+        if s_t_y > f64::EPSILON*s.norm()*y.norm() {
+            if history.len() == m {
+                history.pop_front();
+            }
+            history.push_back((s, y, 1.0/s_t_y));
+        }
+    }
+    Ok(SolverResult::new(x, f_x, max_iter, n_fev, TerminationReason::MaxIterations))
+}
+
+/// Limited-memory BFGS (L-BFGS) method for minimizing a function f(x)
+/// @param f function to minimize
+/// @param x0 initial guess
+/// @param m number of correction pairs (s, y) kept in memory (usually between 3 and 20)
+/// @param tol tolerance on the norm of the gradient and on the norm of the step
+/// @param dx_num numerical differentiation step size
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note This method uses central finite-differences to compute the gradient.
+pub fn lbfgs_minimize_num<F>(f: F, x0: &na::DVector<f64>, m: usize, tol: f64, dx_num: f64, max_iter: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError>
+where F : Fn(&na::DVector<f64>) -> f64
+{
+    let n_grad: Cell<u32> = Cell::new(0);
+    let mut res: SolverResult<na::DVector<f64>> = lbfgs_minimize(&f, |x: &na::DVector<f64>| {
+        n_grad.set(n_grad.get() + 1);
+        finite_differences::gradient_num(&f, x, dx_num)
+    }, x0, m, tol, max_iter)?;
+    res.n_fev += 2*(x0.len() as u32)*n_grad.get();// Two evaluations per dimension for each gradient
+    Ok(res)
+}
+
+/// Compact representation B = theta*I - W*M*W^T of the L-BFGS approximation of the Hessian (Byrd, Lu, Nocedal & Zhu, 1995).
+struct CompactHessian {
+    theta: f64,              // scaling of the initial approximation
+    w: na::DMatrix<f64>,     // W = [Y, theta*S], of size n x 2m
+    m: na::DMatrix<f64>,     // middle matrix M, of size 2m x 2m
+}
+
+/// Builds the compact representation of the Hessian approximation from the correction pairs.
+/// @param history correction pairs (s, y), oldest first
+/// @return compact representation of B, or None if the middle matrix is singular
+fn compact_hessian(history: &VecDeque<(na::DVector<f64>, na::DVector<f64>)>, n_dims: usize) -> Option<CompactHessian> {
+    let k: usize = history.len();
+    let theta: f64 = match history.back() {
+        Some((s, y)) => y.norm_squared()/s.dot(y),
+        None => 1.0,
+    };
+    let mut w: na::DMatrix<f64> = na::DMatrix::zeros(n_dims, 2*k);
+    for (i, (s, y)) in history.iter().enumerate() {
+        w.set_column(i, y);
+        w.set_column(k + i, &(theta*s));
+    }
+
+    // M = [-D, L^T ; L, theta*S^T*S]^-1, where D = diag(s_i^T*y_i) and L is the strictly lower triangular part of S^T*Y
+    let mut m_inv: na::DMatrix<f64> = na::DMatrix::zeros(2*k, 2*k);
+    for (i, (s_i, y_i)) in history.iter().enumerate() {
+        m_inv[(i, i)] = -s_i.dot(y_i);
+        for (j, (s_j, y_j)) in history.iter().enumerate() {
+            if i > j {
+                let l_ij: f64 = s_i.dot(y_j);
+                m_inv[(k + i, j)] = l_ij;
+                m_inv[(j, k + i)] = l_ij;
+            }
+            m_inv[(k + i, k + j)] = theta*s_i.dot(s_j);
+        }
+    }
+    let m: na::DMatrix<f64> = m_inv.try_inverse()?;
+    Some(CompactHessian { theta, w, m })
+}
+
+/// Generalized Cauchy point : first local minimizer of the quadratic model along the projected steepest descent path x(t) = P(x - t*g).
+/// @param x current point
+/// @param g gradient at x
+/// @param lb lower bounds
+/// @param ub upper bounds
+/// @param b compact representation of the Hessian approximation
+/// @return the Cauchy point and the vector c = W^T*(x_cp - x)
+fn generalized_cauchy_point(x: &na::DVector<f64>, g: &na::DVector<f64>, lb: &na::DVector<f64>, ub: &na::DVector<f64>, b: &CompactHessian) -> (na::DVector<f64>, na::DVector<f64>) {
+    let n_dims: usize = x.len();
+
+    // Breakpoints t_i at which each variable reaches its bound
+    let mut t: na::DVector<f64> = na::DVector::from_element(n_dims, f64::INFINITY);
+    let mut d: na::DVector<f64> = na::DVector::zeros(n_dims);
+    for i in 0..n_dims {
+        if g[i] < 0.0 {
+            t[i] = (x[i] - ub[i])/g[i];
+        } else if g[i] > 0.0 {
+            t[i] = (x[i] - lb[i])/g[i];
+        }
+        if t[i] > 0.0 {
+            d[i] = -g[i];
+        }
+    }
+    let mut breakpoints: Vec<usize> = (0..n_dims).filter(|&i| t[i] > 0.0 && t[i].is_finite()).collect();
+    breakpoints.sort_by(|&i, &j| t[i].partial_cmp(&t[j]).unwrap());
+
+    // Derivatives of the quadratic model along the path
+    let mut x_cp: na::DVector<f64> = x.clone();
+    let mut p: na::DVector<f64> = b.w.transpose()*&d;
+    let mut c: na::DVector<f64> = na::DVector::zeros(p.len());
+    let mut df: f64 = -d.norm_squared();
+    let mut ddf: f64 = -b.theta*df - p.dot(&(&b.m*&p));
+    let ddf_min: f64 = f64::EPSILON*ddf;
+    let mut dt_min: f64 = -df/ddf;
+    let mut t_old: f64 = 0.0;
+
+    // Explore the segments of the path until the minimizer of the model lies inside the current segment
+    for &i in breakpoints.iter() {
+        let dt: f64 = t[i] - t_old;
+        if dt_min < dt {
+            break;
+        }
+        x_cp[i] = if d[i] > 0.0 { ub[i] } else { lb[i] };
+        let z_i: f64 = x_cp[i] - x[i];
+        c += dt*&p;
+        let w_i: na::DVector<f64> = b.w.row(i).transpose();
+        let m_w_i: na::DVector<f64> = &b.m*&w_i;
+        df += dt*ddf + g[i]*g[i] + b.theta*g[i]*z_i - g[i]*m_w_i.dot(&c);
+        ddf += -b.theta*g[i]*g[i] - 2.0*g[i]*m_w_i.dot(&p) - g[i]*g[i]*w_i.dot(&m_w_i);
+        ddf = f64::max(ddf, ddf_min);
+        p.axpy(g[i], &w_i, 1.0);
+        d[i] = 0.0;
+        dt_min = -df/ddf;
+        t_old = t[i];
+    }
+    dt_min = f64::max(dt_min, 0.0);
+    t_old += dt_min;
+    for i in 0..n_dims {
+        if d[i] != 0.0 {
+            x_cp[i] = (x[i] + t_old*d[i]).clamp(lb[i], ub[i]);
+        }
+    }
+    c.axpy(dt_min, &p, 1.0);
+    (x_cp, c)
+}
+
+/// Minimizes the quadratic model over the variables that are free at the Cauchy point (direct primal method), and truncates the step to stay inside the bounds.
+/// @param x current point
+/// @param g gradient at x
+/// @param lb lower bounds
+/// @param ub upper bounds
+/// @param x_cp generalized Cauchy point
+/// @param c vector W^T*(x_cp - x)
+/// @param b compact representation of the Hessian approximation
+/// @return the point minimizing the model in the subspace, or the Cauchy point if the reduced system is singular
+fn subspace_minimization(x: &na::DVector<f64>, g: &na::DVector<f64>, lb: &na::DVector<f64>, ub: &na::DVector<f64>, x_cp: &na::DVector<f64>, c: &na::DVector<f64>, b: &CompactHessian) -> na::DVector<f64> {
+    let free: Vec<usize> = (0..x.len()).filter(|&i| x_cp[i] > lb[i] && x_cp[i] < ub[i]).collect();
+    if free.is_empty() {
+        return x_cp.clone();
+    }
+
+    // Reduced gradient of the model at the Cauchy point : r = Z^T*(g + theta*(x_cp - x) - W*M*c)
+    let m_c: na::DVector<f64> = &b.m*c;
+    let w_z: na::DMatrix<f64> = b.w.select_rows(free.iter());
+    let r: na::DVector<f64> = na::DVector::from_fn(free.len(), |k, _| {
+        let i: usize = free[k];
+        g[i] + b.theta*(x_cp[i] - x[i]) - b.w.row(i).transpose().dot(&m_c)
+    });
+
+    // Reduced Newton step d = -B_z^-1*r, where B_z^-1 is given by the Sherman-Morrison-Woodbury formula
+    let mut du: na::DVector<f64> = -&r/b.theta;
+    if b.m.nrows() > 0 {
+        let n_mat: na::DMatrix<f64> = na::DMatrix::identity(b.m.nrows(), b.m.nrows()) - (1.0/b.theta)*&b.m*w_z.transpose()*&w_z;
+        let v: na::DVector<f64> = &b.m*(w_z.transpose()*&r);
+        match n_mat.lu().solve(&v) {
+            Some(v) => du -= (1.0/(b.theta*b.theta))*(&w_z*v),
+            None => return x_cp.clone(),
+        }
+    }
+
+    // Largest step alpha in [0, 1] keeping x_cp + alpha*d inside the bounds
+    let mut alpha: f64 = 1.0;
+    for (k, &i) in free.iter().enumerate() {
+        if du[k] > 0.0 {
+            alpha = f64::min(alpha, (ub[i] - x_cp[i])/du[k]);
+        } else if du[k] < 0.0 {
+            alpha = f64::min(alpha, (lb[i] - x_cp[i])/du[k]);
+        }
+    }
+    let mut x_bar: na::DVector<f64> = x_cp.clone();
+    for (k, &i) in free.iter().enumerate() {
+        x_bar[i] = (x_cp[i] + alpha*du[k]).clamp(lb[i], ub[i]);
+    }
+    x_bar
+}
+
+/// L-BFGS-B method for minimizing a function f(x) subject to bounds lb <= x <= ub
+/// @param f function to minimize
+/// @param grad gradient of f
+/// @param x0 initial guess, projected onto the bounds if it lies outside of them
+/// @param lb lower bounds (may be -infinity)
+/// @param ub upper bounds (may be +infinity)
+/// @param m number of correction pairs (s, y) kept in memory (usually between 3 and 20)
+/// @param tol tolerance on the norm of the projected gradient and on the norm of the step
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note Each iteration computes the generalized Cauchy point along the projected steepest descent path, minimizes the quadratic model over the variables that are not at their bounds, and performs a More-Thuente line search along the resulting direction (Byrd, Lu, Nocedal & Zhu, 1995).
+/// @note If the line search fails, the current iterate is returned with termination = LineSearchFailure and converged = false.
+#[allow(clippy::too_many_arguments)]
+pub fn lbfgsb_minimize<F, G>(f: F, grad: G, x0: &na::DVector<f64>, lb: &na::DVector<f64>, ub: &na::DVector<f64>, m: usize, tol: f64, max_iter: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError>
+where F : Fn(&na::DVector<f64>) -> f64, G : Fn(&na::DVector<f64>) -> na::DVector<f64>
+{
+    // Parameters
+    let c1: f64 = 1e-3;            // Sufficient decrease coefficient of the line search
+    let c2: f64 = 0.9;             // Curvature coefficient of the line search
+    let max_iter_line_search: u32 = 20;
+
+    let n_dims: usize = x0.len();
+    if lb.len() != n_dims || ub.len() != n_dims {
+        return Err(SolverError::InvalidInput("lb and ub must have as many components as x0"));
+    }
+    if lb.iter().zip(ub.iter()).any(|(l, u)| l > u) {
+        return Err(SolverError::InvalidInput("lb must be lower than or equal to ub"));
+    }
+    if m == 0 {
+        return Err(SolverError::InvalidInput("the history size m must be at least 1"));
+    }
+    let mut x: na::DVector<f64> = na::DVector::from_fn(n_dims, |i, _| x0[i].clamp(lb[i], ub[i]));
+    let mut f_x: f64 = f(&x);
+    let mut g_x: na::DVector<f64> = grad(&x);
+    let mut n_fev: u32 = 1;
+    if g_x.len() != n_dims {
+        return Err(SolverError::InvalidInput("the gradient must have as many components as x"));
+    }
+    let mut history: VecDeque<(na::DVector<f64>, na::DVector<f64>)> = VecDeque::with_capacity(m);// Correction pairs (s, y), oldest first
+
+    for iter in 0..max_iter {
+        if !f_x.is_finite() || g_x.iter().any(|gi| !gi.is_finite()) {
+            return Err(SolverError::NonFiniteValue);
+        }
+        // Projected gradient : zero at a point satisfying the first-order optimality conditions
+        let pg_norm: f64 = (0..n_dims).map(|i| ((x[i] - g_x[i]).clamp(lb[i], ub[i]) - x[i]).abs()).fold(0.0, f64::max);
+        if pg_norm < tol {
+            return Ok(SolverResult::new(x, f_x, iter, n_fev, TerminationReason::GradientTolerance));
+        }
+
+        // Search direction towards the minimizer of the quadratic model, reset to the projected steepest descent if it is not a descent direction
+        let mut b: Option<CompactHessian> = compact_hessian(&history, n_dims);
+        if b.is_none() {
+            history.clear();
+            b = compact_hessian(&history, n_dims);
+        }
+        let (x_cp, c) = generalized_cauchy_point(&x, &g_x, lb, ub, b.as_ref().unwrap());
+        let mut p: na::DVector<f64> = subspace_minimization(&x, &g_x, lb, ub, &x_cp, &c, b.as_ref().unwrap()) - &x;
+        if g_x.dot(&p) >= 0.0 && !history.is_empty() {
+            history.clear();
+            let b: CompactHessian = compact_hessian(&history, n_dims).unwrap();
+            let (x_cp, _) = generalized_cauchy_point(&x, &g_x, lb, ub, &b);
+            p = x_cp - &x;
+        }
+        let dphi0: f64 = g_x.dot(&p);
+        if dphi0.is_nan() || dphi0 >= 0.0 {
+            return Ok(SolverResult::new(x, f_x, iter, n_fev, TerminationReason::StepTolerance));
+        }
+
+        // Without curvature information, the first step is scaled so that its length does not exceed 1 and may go up to the bounds.
+        // Otherwise the full step leads to the minimizer of the model, which lies inside the bounds.
+        let (alpha0, alpha_max) = if history.is_empty() {
+            let mut alpha_max: f64 = f64::INFINITY;
+            for i in 0..n_dims {
+                if p[i] > 0.0 {
+                    alpha_max = f64::min(alpha_max, (ub[i] - x[i])/p[i]);
+                } else if p[i] < 0.0 {
+                    alpha_max = f64::min(alpha_max, (lb[i] - x[i])/p[i]);
+                }
+            }
+            (f64::min(1.0/p.norm(), alpha_max), alpha_max)
+        } else {
+            (1.0, 1.0)
+        };
+        let point = match line_search::more_thuente_line_search(&f, &grad, &x, f_x, &g_x, &p, alpha0, alpha_max, c1, c2, max_iter_line_search, &mut n_fev) {
+            Ok(point) => point,
+            // No sufficient decrease was found along the direction of the model : x, which always lies inside the bounds, is the best point found
+            Err(_) => return Ok(SolverResult::new(x, f_x, iter, n_fev, TerminationReason::LineSearchFailure)),
+        };
+        let s: na::DVector<f64> = point.alpha*&p;
+        let y: na::DVector<f64> = &point.grad - &g_x;
+        x = point.x;
+        f_x = point.f_x;
+        g_x = point.grad;
+
+        if s.norm() < tol {
+            return Ok(SolverResult::new(x, f_x, iter + 1, n_fev, TerminationReason::StepTolerance));
+        }
+
+        // Store the correction pair, dropping the oldest one, unless the curvature condition does not hold
+        if s.dot(&y) > f64::EPSILON*y.norm_squared() {
+            if history.len() == m {
+                history.pop_front();
+            }
+            history.push_back((s, y));
+        }
+    }
+    Ok(SolverResult::new(x, f_x, max_iter, n_fev, TerminationReason::MaxIterations))
+}
+
+/// L-BFGS-B method for minimizing a function f(x) subject to bounds lb <= x <= ub
+/// @param f function to minimize
+/// @param x0 initial guess, projected onto the bounds if it lies outside of them
+/// @param lb lower bounds (may be -infinity)
+/// @param ub upper bounds (may be +infinity)
+/// @param m number of correction pairs (s, y) kept in memory (usually between 3 and 20)
+/// @param tol tolerance on the norm of the projected gradient and on the norm of the step
+/// @param dx_num numerical differentiation step size
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note This method uses central finite-differences to compute the gradient, so f may be evaluated up to dx_num outside of the bounds.
+#[allow(clippy::too_many_arguments)]
+pub fn lbfgsb_minimize_num<F>(f: F, x0: &na::DVector<f64>, lb: &na::DVector<f64>, ub: &na::DVector<f64>, m: usize, tol: f64, dx_num: f64, max_iter: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError>
+where F : Fn(&na::DVector<f64>) -> f64
+{
+    let n_grad: Cell<u32> = Cell::new(0);
+    let mut res: SolverResult<na::DVector<f64>> = lbfgsb_minimize(&f, |x: &na::DVector<f64>| {
+        n_grad.set(n_grad.get() + 1);
+        finite_differences::gradient_num(&f, x, dx_num)
+    }, x0, lb, ub, m, tol, max_iter)?;
+    res.n_fev += 2*(x0.len() as u32)*n_grad.get();// Two evaluations per dimension for each gradient
+    Ok(res)
+}
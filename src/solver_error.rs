@@ -0,0 +1,38 @@
+use std::fmt;
+
+/// Errors returned by the solvers and optimizers of the library.
+/// @note Root finders return MaxIterations when they run out of iterations, since an unconverged root is of no use.
+/// Optimizers and least-squares solvers instead return their best point with converged = false in the SolverResult.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverError {
+    /// f(a) and f(b) have the same sign, so the interval [a, b] does not bracket a root.
+    NotBracketed,
+    /// The maximum number of iterations was reached before convergence.
+    MaxIterations,
+    /// The derivative (or the denominator of the update formula) vanished, so no step can be taken.
+    ZeroDerivative,
+    /// The function returned a NaN or infinite value.
+    NonFiniteValue,
+    /// The Jacobian matrix (or J^T J) is singular, so the linear system of the step cannot be solved.
+    SingularJacobian,
+    /// The step became smaller than the tolerance while the function is not close to zero (e.g. near a local minimum of ||F|| that is not a root).
+    NoProgress,
+    /// The arguments given to the solver are invalid.
+    InvalidInput(&'static str),
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolverError::NotBracketed => write!(f, "The interval does not bracket the root."),
+            SolverError::MaxIterations => write!(f, "Maximum number of iterations exceeded."),
+            SolverError::ZeroDerivative => write!(f, "The derivative vanished, no step can be taken."),
+            SolverError::NonFiniteValue => write!(f, "The function returned a non-finite value."),
+            SolverError::SingularJacobian => write!(f, "The Jacobian matrix is singular."),
+            SolverError::NoProgress => write!(f, "The step vanished before the function reached zero."),
+            SolverError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SolverError {}
@@ -0,0 +1,52 @@
+/// Reason why a solver or an optimizer stopped iterating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminationReason {
+    /// The function is exactly zero at the solution.
+    ExactRoot,
+    /// The step size (or the size of the bracket, simplex, ...) fell below the tolerance.
+    StepTolerance,
+    /// The function value (or the spread of the function values) fell below the tolerance.
+    FunctionTolerance,
+    /// The norm of the gradient fell below the tolerance.
+    GradientTolerance,
+    /// The maximum number of iterations was reached before any convergence criterion was met.
+    MaxIterations,
+    /// The line search could not find an acceptable step along a descent direction before any convergence criterion was met.
+    LineSearchFailure,
+    /// The trust region shrank below the tolerance before any convergence criterion was met.
+    TrustRegionCollapse,
+}
+
+impl TerminationReason {
+    /// @brief Tells whether the reason corresponds to a met convergence criterion
+    /// @return false for MaxIterations, LineSearchFailure and TrustRegionCollapse, true otherwise
+    pub fn is_converged(self) -> bool {
+        !matches!(self, TerminationReason::MaxIterations | TerminationReason::LineSearchFailure | TerminationReason::TrustRegionCollapse)
+    }
+}
+
+/// Report returned by every solver and optimizer of the library.
+/// @note n_fev counts the evaluations of the function to solve or minimize (including those used for finite-differences derivatives), but not the evaluations of user-supplied derivatives.
+#[derive(Debug, Clone)]
+pub struct SolverResult<T> {
+    pub x: T,                             // solution
+    pub f_x: f64,                         // function value at the solution (objective value, or sum of squared residuals for least-squares)
+    pub n_iter: u32,                      // number of iterations performed
+    pub n_fev: u32,                       // number of function evaluations
+    pub termination: TerminationReason,   // reason why the solver stopped
+    pub converged: bool,                  // true if a convergence criterion was met
+}
+
+impl<T> SolverResult<T> {
+    /// Creates a new result. The solver is considered converged unless it stopped on the maximum number of iterations, on a line search failure or on a collapse of the trust region.
+    pub fn new(x: T, f_x: f64, n_iter: u32, n_fev: u32, termination: TerminationReason) -> SolverResult<T> {
+        SolverResult {
+            x,
+            f_x,
+            n_iter,
+            n_fev,
+            termination,
+            converged: termination.is_converged(),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+extern crate nalgebra as na;
+
+use std::cell::Cell;
+
+use crate::finite_differences;
+use crate::solver_error::SolverError;
+use crate::solver_result::{SolverResult, TerminationReason};
+
+/// Positive step length tau such that |z + tau*d| = delta.
+fn boundary_step(z: &na::DVector<f64>, d: &na::DVector<f64>, delta: f64) -> f64 {
+    let a: f64 = d.norm_squared();
+    let b: f64 = 2.0*z.dot(d);
+    let c: f64 = z.norm_squared() - delta*delta;
+    (-b + f64::sqrt(f64::max(b*b - 4.0*a*c, 0.0)))/(2.0*a)
+}
+
+/// Approximately minimizes the quadratic model m(p) = g^T*p + 0.5*p^T*B*p inside the trust region |p| <= delta with the truncated conjugate gradient method of Steihaug (Nocedal & Wright, algorithm 7.2).
+/// @param g gradient
+/// @param b Hessian matrix, which may be indefinite
+/// @param delta trust-region radius
+/// @return step p
+/// @note The iterations stop on the boundary of the trust region when a direction of negative curvature is found or when the step leaves the trust region.
+fn steihaug_cg(g: &na::DVector<f64>, b: &na::DMatrix<f64>, delta: f64) -> na::DVector<f64> {
+    let g_norm: f64 = g.norm();
+    let tol: f64 = f64::min(0.5, g_norm.sqrt())*g_norm;// Forcing sequence giving superlinear convergence
+    let mut z: na::DVector<f64> = na::DVector::zeros(g.len());
+    let mut r: na::DVector<f64> = g.clone();
+    let mut d: na::DVector<f64> = -g;
+    for _ in 0..2*g.len() {
+        let b_d: na::DVector<f64> = b*&d;
+        let d_b_d: f64 = d.dot(&b_d);
+        if d_b_d <= 0.0 {
+            // Negative curvature : follow d up to the boundary
+            let tau: f64 = boundary_step(&z, &d, delta);
+            return z + tau*d;
+        }
+        let r_t_r: f64 = r.norm_squared();
+        let alpha: f64 = r_t_r/d_b_d;
+        let z_new: na::DVector<f64> = &z + alpha*&d;
+        if z_new.norm() >= delta {
+            let tau: f64 = boundary_step(&z, &d, delta);
+            return z + tau*d;
+        }
+        z = z_new;
+        r += alpha*b_d;
+        if r.norm() < tol {
+            break;
+        }
+        let beta: f64 = r.norm_squared()/r_t_r;
+        d = -&r + beta*d;
+    }
+    z
+}
+
+/// Newton's trust-region method for minimizing a function f(x)
+/// @param f function to minimize
+/// @param grad gradient of f
+/// @param hess Hessian matrix of f
+/// @param x0 initial guess
+/// @param tol tolerance on the norm of the gradient, on the norm of the step and on the trust-region radius
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note The subproblem is solved with the truncated conjugate gradient method of Steihaug, which does not require the Hessian to be positive definite : directions of negative curvature are followed up to the boundary of the trust region.
+/// @note The Hessian is evaluated only once per iteration and never factorized, but it is used in matrix-vector products by the subproblem solver.
+/// @note If the trust region collapses before the gradient is small enough, the current iterate is returned with termination = TrustRegionCollapse and converged = false.
+pub fn newton_trust_region_minimize<F, G, H>(f: F, grad: G, hess: H, x0: &na::DVector<f64>, tol: f64, max_iter: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError>
+where F : Fn(&na::DVector<f64>) -> f64, G : Fn(&na::DVector<f64>) -> na::DVector<f64>, H : Fn(&na::DVector<f64>) -> na::DMatrix<f64>
+{
+    // Parameters
+    let delta0: f64 = 1.0;         // Initial trust-region radius
+    let delta_max: f64 = 1e3;      // Largest trust-region radius
+    let eta: f64 = 0.1;            // Smallest ratio of actual to predicted reduction for a step to be accepted
+
+    let n_dims: usize = x0.len();
+    let mut x: na::DVector<f64> = x0.clone();
+    let mut f_x: f64 = f(&x);
+    let mut g_x: na::DVector<f64> = grad(&x);
+    let mut h_x: na::DMatrix<f64> = hess(&x);
+    let mut n_fev: u32 = 1;
+    if g_x.len() != n_dims || h_x.nrows() != n_dims || h_x.ncols() != n_dims {
+        return Err(SolverError::InvalidInput("the gradient and the Hessian must have as many rows as x"));
+    }
+    let mut delta: f64 = delta0;
+
+    for iter in 0..max_iter {
+        if !f_x.is_finite() || g_x.iter().any(|gi| !gi.is_finite()) || h_x.iter().any(|hij| !hij.is_finite()) {
+            return Err(SolverError::NonFiniteValue);
+        }
+        if g_x.amax() < tol {
+            return Ok(SolverResult::new(x, f_x, iter, n_fev, TerminationReason::GradientTolerance));
+        }
+
+        // Ratio of the actual reduction of f to the reduction predicted by the quadratic model
+        let p: na::DVector<f64> = steihaug_cg(&g_x, &h_x, delta);
+        let p_norm: f64 = p.norm();
+        let predicted: f64 = -(g_x.dot(&p) + 0.5*p.dot(&(&h_x*&p)));
+        let x_new: na::DVector<f64> = &x + &p;
+        let f_new: f64 = f(&x_new);
+        n_fev += 1;
+        let rho: f64 = if f_new.is_finite() { (f_x - f_new)/predicted } else { f64::NEG_INFINITY };
+
+        // Update of the trust-region radius
+        if rho < 0.25 {
+            delta = 0.25*f64::min(delta, p_norm);
+        } else if rho > 0.75 && p_norm >= 0.99*delta {
+            delta = f64::min(2.0*delta, delta_max);
+        }
+
+        if rho > eta {
+            x = x_new;
+            f_x = f_new;
+            g_x = grad(&x);
+            h_x = hess(&x);
+            if p_norm < tol {
+                return Ok(SolverResult::new(x, f_x, iter + 1, n_fev, TerminationReason::StepTolerance));
+            }
+        } else if delta < tol {
+            // The trust region collapsed while the gradient is still above the tolerance : x is the best point found
+            return Ok(SolverResult::new(x, f_x, iter + 1, n_fev, TerminationReason::TrustRegionCollapse));
+        }
+    }
+    Ok(SolverResult::new(x, f_x, max_iter, n_fev, TerminationReason::MaxIterations))
+}
+
+/// Newton's trust-region method for minimizing a function f(x)
+/// @param f function to minimize
+/// @param x0 initial guess
+/// @param tol tolerance on the norm of the gradient, on the norm of the step and on the trust-region radius
+/// @param dx_num numerical differentiation step size of the gradient
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note This method uses central finite-differences to compute the gradient and the Hessian. The step size of the Hessian is at least the cubic root of the machine epsilon, as recommended by hessian_num.
+pub fn newton_trust_region_minimize_num<F>(f: F, x0: &na::DVector<f64>, tol: f64, dx_num: f64, max_iter: u32) -> Result<SolverResult<na::DVector<f64>>, SolverError>
+where F : Fn(&na::DVector<f64>) -> f64
+{
+    let dx_hess: f64 = f64::max(dx_num, f64::EPSILON.cbrt());
+    let n_grad: Cell<u32> = Cell::new(0);
+    let n_hess: Cell<u32> = Cell::new(0);
+    let mut res: SolverResult<na::DVector<f64>> = newton_trust_region_minimize(&f, |x: &na::DVector<f64>| {
+        n_grad.set(n_grad.get() + 1);
+        finite_differences::gradient_num(&f, x, dx_num)
+    }, |x: &na::DVector<f64>| {
+        n_hess.set(n_hess.get() + 1);
+        finite_differences::hessian_num(&f, x, dx_hess)
+    }, x0, tol, max_iter)?;
+    let n_dims: u32 = x0.len() as u32;
+    res.n_fev += 2*n_dims*n_grad.get() + (2*n_dims*n_dims + 1)*n_hess.get();
+    Ok(res)
+}
@@ -1,21 +1,27 @@
+use crate::solver_error::SolverError;
+use crate::solver_result::{SolverResult, TerminationReason};
+
 /// Golden section search for minimizing a function f(x)
 /// @param f function to minimize
 /// @param a left bracket
 /// @param b right bracket
 /// @param tol tolerance
-/// @return solution
+/// @return solution report
 /// @note The interval [a, b] must bracket the minimum, and the function must have f''(x) > 0 over the interval [a, b] to garantee convergence.
-pub fn golden_section_minimize<F>(f : F, mut a: f64, mut b: f64, tol: f64) -> f64
+pub fn golden_section_minimize<F>(f : F, mut a: f64, mut b: f64, tol: f64) -> Result<SolverResult<f64>, SolverError>
 where F : Fn(f64) -> f64
 {
     let invphi: f64 = (f64::sqrt(5.0) - 1.0) / 2.0;   // 1 / phi
     let invphi2: f64 = (3.0 - f64::sqrt(5.0)) / 2.0;  // 1 / phi^2
 
-    a = f64::min(a, b);
-    b = f64::max(a, b);
+    if tol <= 0.0 {
+        return Err(SolverError::InvalidInput("the tolerance must be strictly positive"));
+    }
+    (a, b) = (f64::min(a, b), f64::max(a, b));
     let mut h: f64 = b - a;
     if h <= tol {
-        return (a + b)/2.0;
+        let x: f64 = (a + b)/2.0;
+        return Ok(SolverResult::new(x, f(x), 0, 1, TerminationReason::StepTolerance));
     }
 
     // Required steps to achieve tolerance
@@ -25,6 +31,7 @@ where F : Fn(f64) -> f64
     let mut d: f64 = a + invphi * h;
     let mut yc: f64 = f(c);
     let mut yd: f64 = f(d);
+    let mut n_fev: u32 = 2;
 
     for _ in 0..n {
         if yc < yd {  // yc > yd to find the maximum
@@ -44,6 +51,353 @@ where F : Fn(f64) -> f64
         }
     }
 
-    if yc < yd { return (a + d)/2.0; }
-    else { return (c + b)/2.0; }
+    let x: f64 = if yc < yd { (a + d)/2.0 } else { (c + b)/2.0 };
+    n_fev += n + 1;
+    Ok(SolverResult::new(x, f(x), n, n_fev, TerminationReason::StepTolerance))
+}
+
+/// Brent's method for minimizing a function f(x)
+/// @param f function to minimize
+/// @param a left bracket
+/// @param b right bracket
+/// @param tol tolerance
+/// @return solution report
+/// @note The method combines golden section steps with successive parabolic interpolation, which converges superlinearly for smooth functions. It stops when the interval containing the minimum is smaller than tol.
+/// @note The interval [a, b] must bracket the minimum, and the function must be unimodal over the interval [a, b] to garantee convergence.
+pub fn brent_minimize<F>(f : F, mut a: f64, mut b: f64, tol: f64) -> Result<SolverResult<f64>, SolverError>
+where F : Fn(f64) -> f64
+{
+    let invphi2: f64 = (3.0 - f64::sqrt(5.0)) / 2.0;  // 1 / phi^2
+
+    if tol <= 0.0 {
+        return Err(SolverError::InvalidInput("the tolerance must be strictly positive"));
+    }
+    (a, b) = (f64::min(a, b), f64::max(a, b));
+    let x: f64 = a + invphi2*(b - a);
+    brent_minimize_from(&f, a, b, x, f(x), 1, tol)
+}
+
+/// Iterations of Brent's method, starting from a point x inside the bracket [a, b] where f has already been evaluated.
+fn brent_minimize_from<F>(f : F, mut a: f64, mut b: f64, mut x: f64, mut f_x: f64, mut n_fev: u32, tol: f64) -> Result<SolverResult<f64>, SolverError>
+where F : Fn(f64) -> f64
+{
+    let invphi2: f64 = (3.0 - f64::sqrt(5.0)) / 2.0;  // 1 / phi^2
+    let max_iter: u32 = 500;
+
+    // x is the best point so far, w the second best and v the previous value of w
+    if !f_x.is_finite() {
+        return Err(SolverError::NonFiniteValue);
+    }
+    let (mut w, mut f_w) = (x, f_x);
+    let (mut v, mut f_v) = (x, f_x);
+    let mut d: f64 = 0.0;// Last step
+    let mut e: f64 = 0.0;// Step before the last one
+
+    for iter in 0..max_iter {
+        let m: f64 = 0.5*(a + b);
+        let tol1: f64 = 0.5*tol + f64::EPSILON*x.abs();
+        let tol2: f64 = 2.0*tol1;
+        if (x - m).abs() <= tol2 - 0.5*(b - a) {
+            return Ok(SolverResult::new(x, f_x, iter, n_fev, TerminationReason::StepTolerance));
+        }
+
+        // Try a parabolic step through x, w and v, falling back to a golden section step if it is not acceptable
+        let mut golden: bool = true;
+        if e.abs() > tol1 {
+            let r: f64 = (x - w)*(f_x - f_v);
+            let mut q: f64 = (x - v)*(f_x - f_w);
+            let mut p: f64 = (x - v)*q - (x - w)*r;
+            q = 2.0*(q - r);
+            if q > 0.0 {
+                p = -p;
+            }
+            q = q.abs();
+            // The parabolic step must be smaller than half the step before the last one, and stay inside [a, b]
+            if p.abs() < (0.5*q*e).abs() && p > q*(a - x) && p < q*(b - x) {
+                e = d;
+                d = p/q;
+                let u: f64 = x + d;
+                if u - a < tol2 || b - u < tol2 {
+                    d = f64::copysign(tol1, m - x);
+                }
+                golden = false;
+            }
+        }
+        if golden {
+            e = if x < m { b - x } else { a - x };
+            d = invphi2*e;
+        }
+
+        // Never evaluate f closer than tol1 to x
+        let u: f64 = if d.abs() >= tol1 { x + d } else { x + f64::copysign(tol1, d) };
+        let f_u: f64 = f(u);
+        n_fev += 1;
+        if !f_u.is_finite() {
+            return Err(SolverError::NonFiniteValue);
+        }
+
+        // Update the bracket and the points x, w and v
+        if f_u <= f_x {
+            if u < x { b = x; } else { a = x; }
+            (v, f_v) = (w, f_w);
+            (w, f_w) = (x, f_x);
+            (x, f_x) = (u, f_u);
+        } else {
+            if u < x { a = u; } else { b = u; }
+            if f_u <= f_w || w == x {
+                (v, f_v) = (w, f_w);
+                (w, f_w) = (u, f_u);
+            } else if f_u <= f_v || v == x || v == w {
+                (v, f_v) = (u, f_u);
+            }
+        }
+    }
+    Ok(SolverResult::new(x, f_x, max_iter, n_fev, TerminationReason::MaxIterations))
+}
+
+/// Local minimizer of the cubic interpolating four points (Newton form), if the cubic has one.
+/// @param xs abscissas, in increasing order
+/// @param fs function values at xs
+/// @return the local minimizer of the interpolating polynomial, or None if it has no local minimum
+fn cubic_interpolant_minimizer(xs: &[f64; 4], fs: &[f64; 4]) -> Option<f64> {
+    // Divided differences
+    let d01: f64 = (fs[1] - fs[0])/(xs[1] - xs[0]);
+    let d12: f64 = (fs[2] - fs[1])/(xs[2] - xs[1]);
+    let d23: f64 = (fs[3] - fs[2])/(xs[3] - xs[2]);
+    let d012: f64 = (d12 - d01)/(xs[2] - xs[0]);
+    let d123: f64 = (d23 - d12)/(xs[3] - xs[1]);
+    let d0123: f64 = (d123 - d012)/(xs[3] - xs[0]);
+
+    // p'(x0 + t) = a*t^2 + b*t + c
+    let (t1, t2) = (xs[1] - xs[0], xs[2] - xs[0]);
+    let a: f64 = 3.0*d0123;
+    let b: f64 = 2.0*(d012 - d0123*(t1 + t2));
+    let c: f64 = d01 - d012*t1 + d0123*t1*t2;
+    let disc: f64 = b*b - 4.0*a*c;
+    if disc < 0.0 || b + disc.sqrt() <= 0.0 {
+        return None;
+    }
+    // Root of p' where p'' = sqrt(disc) > 0, written to avoid cancellation when a is small
+    let t: f64 = -2.0*c/(b + disc.sqrt());
+    if t.is_finite() { Some(xs[0] + t) } else { None }
+}
+
+/// Cubic Lagrange polynomial interpolation for minimizing a function f(x)
+/// @param f function to minimize
+/// @param a left bracket
+/// @param b right bracket
+/// @param tol tolerance
+/// @return solution report
+/// @note A cubic is fitted through four points bracketing the minimum, and its minimizer replaces one of them. A golden section step is taken instead when the cubic has no minimum inside the bracket or when the bracket shrinks too slowly.
+/// @note The interval [a, b] must bracket the minimum, and the function must be unimodal over the interval [a, b] to garantee convergence. The method stops when the bracket is smaller than tol.
+pub fn cubic_lagrange_minimize<F>(f : F, mut a: f64, mut b: f64, tol: f64) -> Result<SolverResult<f64>, SolverError>
+where F : Fn(f64) -> f64
+{
+    let invphi: f64 = (f64::sqrt(5.0) - 1.0) / 2.0;   // 1 / phi
+    let invphi2: f64 = (3.0 - f64::sqrt(5.0)) / 2.0;  // 1 / phi^2
+    let max_iter: u32 = 500;
+
+    if tol <= 0.0 {
+        return Err(SolverError::InvalidInput("the tolerance must be strictly positive"));
+    }
+    (a, b) = (f64::min(a, b), f64::max(a, b));
+    let h: f64 = b - a;
+    if h <= tol {
+        let x: f64 = (a + b)/2.0;
+        return Ok(SolverResult::new(x, f(x), 0, 1, TerminationReason::StepTolerance));
+    }
+
+    // Four points of the bracket, in increasing order
+    let mut xs: [f64; 4] = [a, a + invphi2*h, a + invphi*h, b];
+    let mut fs: [f64; 4] = [0.0; 4];
+    for i in 0..4 {
+        fs[i] = f(xs[i]);
+        if !fs[i].is_finite() {
+            return Err(SolverError::NonFiniteValue);
+        }
+    }
+    let mut n_fev: u32 = 4;
+    let mut widths: [f64; 2] = [f64::INFINITY; 2];// Widths of the bracket over the last two iterations
+
+    for iter in 0..max_iter {
+        let i_best: usize = (0..4).fold(0, |i, j| if fs[j] < fs[i] { j } else { i });
+        let (x_best, f_best) = (xs[i_best], fs[i_best]);
+        let width: f64 = xs[3] - xs[0];
+        if width <= tol {
+            return Ok(SolverResult::new(x_best, f_best, iter, n_fev, TerminationReason::StepTolerance));
+        }
+
+        // Minimizer of the cubic, rejected if it is outside the bracket or if the bracket shrank by less than half over the last two iterations
+        let delta: f64 = 0.25*tol;// Smallest distance between two points
+        let mut u: f64 = match cubic_interpolant_minimizer(&xs, &fs) {
+            Some(u) if u > xs[0] + delta && u < xs[3] - delta && width <= 0.5*widths[0] => u,
+            _ => {
+                // Golden section step into the larger side of the bracket
+                let far: f64 = if x_best - xs[0] > xs[3] - x_best { xs[0] } else { xs[3] };
+                x_best + invphi2*(far - x_best)
+            },
+        };
+        widths = [widths[1], width];
+        // Never evaluate f closer than delta to an existing point
+        if let Some(x_close) = xs.iter().find(|&&x| (u - x).abs() < delta) {
+            u = if u >= *x_close { x_close + delta } else { x_close - delta };
+            u = u.clamp(xs[0] + delta, xs[3] - delta);
+        }
+        let f_u: f64 = f(u);
+        n_fev += 1;
+        if !f_u.is_finite() {
+            return Err(SolverError::NonFiniteValue);
+        }
+
+        // Keep the four consecutive points around the best one that form the narrowest bracket
+        let mut xs5: Vec<f64> = xs.to_vec();
+        let mut fs5: Vec<f64> = fs.to_vec();
+        let k: usize = xs5.iter().position(|&x| x > u).unwrap_or(4);
+        xs5.insert(k, u);
+        fs5.insert(k, f_u);
+        let i_best5: usize = (0..5).fold(0, |i, j| if fs5[j] < fs5[i] { j } else { i });
+        let start: usize = match i_best5 {
+            0 => 0,
+            4 => 1,
+            1 => 0,
+            3 => 1,
+            _ => if xs5[3] - xs5[0] <= xs5[4] - xs5[1] { 0 } else { 1 },
+        };
+        xs.copy_from_slice(&xs5[start..start + 4]);
+        fs.copy_from_slice(&fs5[start..start + 4]);
+    }
+    let i_best: usize = (0..4).fold(0, |i, j| if fs[j] < fs[i] { j } else { i });
+    Ok(SolverResult::new(xs[i_best], fs[i_best], max_iter, n_fev, TerminationReason::MaxIterations))
+}
+
+/// Triple of points bracketing a minimum of a function : b lies between a and c, with f(b) <= f(a) and f(b) <= f(c).
+#[derive(Debug, Clone, Copy)]
+pub struct MinimumBracket {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub f_a: f64,
+    pub f_b: f64,
+    pub f_c: f64,
+    pub n_fev: u32,    // number of function evaluations used to find the bracket
+}
+
+/// Searches downhill for a triple of points bracketing a minimum of a function f(x) (mnbrak of Numerical Recipes)
+/// @param f function to minimize
+/// @param a starting point
+/// @param b second starting point, giving the initial step. If None, b = a + 0.1*max(1, |a|) is used.
+/// @return bracketing triple
+/// @note The step grows by the golden ratio at each iteration, or is given by a parabolic extrapolation limited to 100 times the current step.
+pub fn bracket_minimum<F>(f : F, a: f64, b: Option<f64>) -> Result<MinimumBracket, SolverError>
+where F : Fn(f64) -> f64
+{
+    let gold: f64 = (1.0 + f64::sqrt(5.0)) / 2.0;  // Default magnification of the step
+    let grow_limit: f64 = 100.0;                   // Largest magnification of the step allowed for a parabolic extrapolation
+    let tiny: f64 = 1e-20;                         // Prevents divisions by zero in the parabolic extrapolation
+    let max_iter: u32 = 100;
+
+    let mut a: f64 = a;
+    let mut b: f64 = b.unwrap_or(a + 0.1*f64::max(1.0, a.abs()));
+    if a == b {
+        return Err(SolverError::InvalidInput("the two starting points must be different"));
+    }
+    let mut f_a: f64 = f(a);
+    let mut f_b: f64 = f(b);
+    let mut n_fev: u32 = 2;
+
+    // Go downhill from a to b
+    if f_b > f_a {
+        (a, b) = (b, a);
+        (f_a, f_b) = (f_b, f_a);
+    }
+    let mut c: f64 = b + gold*(b - a);
+    let mut f_c: f64 = f(c);
+    n_fev += 1;
+
+    for _ in 0..max_iter {
+        if !f_a.is_finite() || !f_b.is_finite() || !f_c.is_finite() {
+            return Err(SolverError::NonFiniteValue);
+        }
+        if f_b <= f_c {
+            return Ok(MinimumBracket { a, b, c, f_a, f_b, f_c, n_fev });
+        }
+
+        // Parabolic extrapolation through a, b and c
+        let r: f64 = (b - a)*(f_b - f_c);
+        let q: f64 = (b - c)*(f_b - f_a);
+        let u_lim: f64 = b + grow_limit*(c - b);
+        let mut u: f64 = b - ((b - c)*q - (b - a)*r)/(2.0*f64::copysign(f64::max((q - r).abs(), tiny), q - r));
+        let mut f_u: f64;
+        if (b - u)*(u - c) > 0.0 {
+            // u is between b and c
+            f_u = f(u);
+            n_fev += 1;
+            if f_u < f_c {
+                return Ok(MinimumBracket { a: b, b: u, c, f_a: f_b, f_b: f_u, f_c, n_fev });
+            } else if f_u > f_b {
+                return Ok(MinimumBracket { a, b, c: u, f_a, f_b, f_c: f_u, n_fev });
+            }
+            // The parabolic step was of no use : use the default magnification
+            u = c + gold*(c - b);
+            f_u = f(u);
+            n_fev += 1;
+        } else if (c - u)*(u - u_lim) > 0.0 {
+            // u is between c and its allowed limit
+            f_u = f(u);
+            n_fev += 1;
+            if f_u < f_c {
+                (b, c) = (c, u);
+                (f_b, f_c) = (f_c, f_u);
+                u = c + gold*(c - b);
+                f_u = f(u);
+                n_fev += 1;
+            }
+        } else if (u - u_lim)*(u_lim - c) >= 0.0 {
+            // u is beyond its allowed limit
+            u = u_lim;
+            f_u = f(u);
+            n_fev += 1;
+        } else {
+            // u is on the wrong side of c
+            u = c + gold*(c - b);
+            f_u = f(u);
+            n_fev += 1;
+        }
+        (a, b, c) = (b, c, u);
+        (f_a, f_b, f_c) = (f_b, f_c, f_u);
+    }
+    Err(SolverError::MaxIterations)
+}
+
+/// Golden section search for minimizing a function f(x), without a bracket of the minimum
+/// @param f function to minimize
+/// @param a starting point
+/// @param b second starting point, giving the initial step of the bracketing. If None, b = a + 0.1*max(1, |a|) is used.
+/// @param tol tolerance
+/// @return solution report, whose evaluation count includes the bracketing
+/// @note The minimum is first bracketed with bracket_minimum, then refined with golden_section_minimize.
+pub fn golden_section_minimize_auto<F>(f : F, a: f64, b: Option<f64>, tol: f64) -> Result<SolverResult<f64>, SolverError>
+where F : Fn(f64) -> f64
+{
+    let bracket: MinimumBracket = bracket_minimum(&f, a, b)?;
+    let mut res: SolverResult<f64> = golden_section_minimize(&f, bracket.a, bracket.c, tol)?;
+    res.n_fev += bracket.n_fev;
+    Ok(res)
+}
+
+/// Brent's method for minimizing a function f(x), without a bracket of the minimum
+/// @param f function to minimize
+/// @param a starting point
+/// @param b second starting point, giving the initial step of the bracketing. If None, b = a + 0.1*max(1, |a|) is used.
+/// @param tol tolerance
+/// @return solution report, whose evaluation count includes the bracketing
+/// @note The minimum is first bracketed with bracket_minimum, then refined with Brent's method starting from the middle point of the bracket.
+pub fn brent_minimize_auto<F>(f : F, a: f64, b: Option<f64>, tol: f64) -> Result<SolverResult<f64>, SolverError>
+where F : Fn(f64) -> f64
+{
+    if tol <= 0.0 {
+        return Err(SolverError::InvalidInput("the tolerance must be strictly positive"));
+    }
+    let bracket: MinimumBracket = bracket_minimum(&f, a, b)?;
+    brent_minimize_from(&f, f64::min(bracket.a, bracket.c), f64::max(bracket.a, bracket.c), bracket.b, bracket.f_b, bracket.n_fev, tol)
 }
@@ -1,31 +1,19 @@
+use crate::solver_error::SolverError;
+use crate::solver_result::{SolverResult, TerminationReason};
+use crate::univariate_minimizers;
+
 /// @brief Newton's method for solving a function f(x) = 0
 /// @param f function to solve
 /// @param df derivative of function f
 /// @param x0 initial guess
 /// @param tol tolerance
 /// @param max_iter maximum number of iterations
-/// @return solution
-pub fn newton_solve<F, F2>(f : F, df : F2, x0 : f64, tol : f64, max_iter : u32) -> f64
+/// @return solution report
+/// @note This is Householder's method of order 1.
+pub fn newton_solve<F, F2>(f : F, df : F2, x0 : f64, tol : f64, max_iter : u32) -> Result<SolverResult<f64>, SolverError>
     where F : Fn(f64) -> f64, F2 : Fn(f64) -> f64
 {
-    let mut x: f64 = x0;
-    let mut dx: f64;
-    let mut fx: f64;
-    let mut dfx: f64;
-    for _iter in 0..max_iter {
-        fx = f(x);
-        dfx = df(x);
-        if dfx == 0.0 {
-            dx = fx;
-        } else {
-            dx = fx/dfx;
-        }
-        x -= dx;
-        if f64::abs(dx) < tol {
-            break;
-        }
-    }
-    return x;
+    householder_solve(|x: f64| vec![f(x), df(x)], 1, x0, tol, max_iter)
 }
 
 /// @brief Newton's method for solving a function f(x) = 0
@@ -34,14 +22,16 @@ pub fn newton_solve<F, F2>(f : F, df : F2, x0 : f64, tol : f64, max_iter : u32)
 /// @param tol tolerance
 /// @param dx_num numerical differentiation step size
 /// @param max_iter maximum number of iterations
-/// @return solution
+/// @return solution report
 /// @note This method uses numerical differentiation to compute the first and second derivatives.
-pub fn newton_solve_num<F>(f : F, x0 : f64, tol : f64, dx_num : f64, max_iter : u32) -> f64
+pub fn newton_solve_num<F>(f : F, x0 : f64, tol : f64, dx_num : f64, max_iter : u32) -> Result<SolverResult<f64>, SolverError>
 where F : Fn(f64) -> f64
 {
-    return newton_solve(&f, |x: f64| {
+    let mut res: SolverResult<f64> = newton_solve(&f, |x: f64| {
         (f(x + dx_num) - f(x - dx_num))/(2.0*dx_num)
-    }, x0, tol, max_iter);
+    }, x0, tol, max_iter)?;
+    res.n_fev *= 3;// Two more evaluations for each derivative
+    Ok(res)
 }
 
 /// Halley's method for solving a function f(x) = 0
@@ -51,63 +41,262 @@ where F : Fn(f64) -> f64
 /// @param x0 initial guess
 /// @param tol tolerance
 /// @param max_iter maximum number of iterations
-/// @return solution
+/// @return solution report
 /// @note This method is more efficient than Newton's method, but requires the second derivative of f
-pub fn halley_solve<F, F2, F3>(f: F, df: F2, ddf: F3, x0: f64, tol: f64, max_iter: u32, verbose: bool) -> Result<f64, &'static str>
+/// @note This is Householder's method of order 2.
+pub fn halley_solve<F, F2, F3>(f: F, df: F2, ddf: F3, x0: f64, tol: f64, max_iter: u32, verbose: bool) -> Result<SolverResult<f64>, SolverError>
+where F : Fn(f64) -> f64, F2 : Fn(f64) -> f64, F3 : Fn(f64) -> f64
+{
+    householder_solve(|x: f64| {
+        let (f_x, df_x, ddf_x) = (f(x), df(x), ddf(x));
+        if verbose {
+            println!("x = {}, f(x) = {}, df(x) = {}, ddf(x) = {}", x, f_x, df_x, ddf_x);
+        }
+        vec![f_x, df_x, ddf_x]
+    }, 2, x0, tol, max_iter)
+}
+
+/// Computes f(x) and its first and second derivatives using central finite-differences.
+/// @param f function
+/// @param x point at which the derivatives are evaluated
+/// @param dx_num numerical differentiation step size
+/// @return (f(x), f'(x), f''(x))
+/// @note This function evaluates f 3 times.
+fn central_derivatives<F>(f: &F, x: f64, dx_num: f64) -> (f64, f64, f64)
+where F : Fn(f64) -> f64
+{
+    let f_x: f64 = f(x);
+    let fx_m_dx: f64 = f(x - dx_num);
+    let fx_p_dx: f64 = f(x + dx_num);
+    let df_x: f64 = (fx_p_dx - fx_m_dx)/(2.0*dx_num);
+    let ddf_x: f64 = (fx_p_dx - 2.0*f_x + fx_m_dx) / (dx_num.powi(2));
+    (f_x, df_x, ddf_x)
+}
+
+/// Halley's method for solving a function f(x) = 0
+/// @param f function to solve
+/// @param x0 initial guess
+/// @param tol tolerance
+/// @param dx_num numerical differentiation step size
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note This method is more efficient than Newton's method, but requires the second derivative of f.
+/// @note This method uses numerical differentiation to compute the first and second derivatives.
+pub fn halley_solve_num<F>(f: F, x0: f64, tol: f64, dx_num : f64, max_iter: u32, verbose: bool) -> Result<SolverResult<f64>, SolverError>
+where F : Fn(f64) -> f64
+{
+    let mut res: SolverResult<f64> = householder_solve(|x: f64| {
+        let (f_x, df_x, ddf_x) = central_derivatives(&f, x, dx_num);
+        if verbose {
+            println!("x = {}, f(x) = {}, df(x) = {}, ddf(x) = {}", x, f_x, df_x, ddf_x);
+        }
+        vec![f_x, df_x, ddf_x]
+    }, 2, x0, tol, max_iter)?;
+    res.n_fev *= 3;// central_derivatives evaluates f 3 times
+    Ok(res)
+}
+
+/// @brief Householder's method of order d for solving a function f(x) = 0
+/// @param f_derivs function returning f(x) and its first d derivatives, in increasing order of derivation
+/// @param d order of the method (1 for Newton's method, 2 for Halley's method)
+/// @param x0 initial guess
+/// @param tol tolerance
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note The step is x_n+1 = x_n + d*(1/f)^(d-1)(x_n)/(1/f)^(d)(x_n), which converges with order d + 1 close to a simple root.
+/// @note The derivatives of 1/f are obtained from the Leibniz rule applied to f*(1/f) = 1, scaled by powers of f so that no division by f occurs.
+pub fn householder_solve<F>(f_derivs : F, d : u32, x0 : f64, tol : f64, max_iter : u32) -> Result<SolverResult<f64>, SolverError>
+where F : Fn(f64) -> Vec<f64>
+{
+    if d == 0 {
+        return Err(SolverError::InvalidInput("the order of the method must be at least 1"));
+    }
+    let d: usize = d as usize;
+    let mut x: f64 = x0;
+    let mut dx: f64;
+    let mut derivs: Vec<f64>;
+    let mut n_fev: u32 = 0;
+    // binomial[m][k] = C(m, k)
+    let mut binomial: Vec<Vec<f64>> = vec![vec![1.0]];
+    for m in 1..=d {
+        let mut row: Vec<f64> = vec![1.0; m + 1];
+        for k in 1..m {
+            row[k] = binomial[m - 1][k - 1] + binomial[m - 1][k];
+        }
+        binomial.push(row);
+    }
+    for iter in 0..max_iter {
+        derivs = f_derivs(x); n_fev += 1;
+        if derivs.len() < d + 1 {
+            return Err(SolverError::InvalidInput("f_derivs must return f and its first d derivatives"));
+        }
+        if derivs[..=d].iter().any(|v| !v.is_finite()) {
+            return Err(SolverError::NonFiniteValue);
+        }
+        let f_x: f64 = derivs[0];
+        if f_x == 0.0 {
+            return Ok(SolverResult::new(x, f_x, iter, n_fev, TerminationReason::ExactRoot));
+        }
+        // v[m] = f^(m+1)*(1/f)^(m), from sum_k C(m, k)*f^(k)*(1/f)^(m-k) = 0 for m >= 1
+        let mut v: Vec<f64> = vec![1.0; d + 1];
+        for m in 1..=d {
+            v[m] = -(1..=m).map(|k| binomial[m][k]*derivs[k]*f_x.powi(k as i32 - 1)*v[m - k]).sum::<f64>();
+        }
+        if v[d] == 0.0 {
+            return Err(SolverError::ZeroDerivative);
+        }
+        dx = (d as f64)*f_x*v[d - 1]/v[d];
+        x += dx;
+        if f64::abs(dx) < tol {
+            return Ok(SolverResult::new(x, f_derivs(x)[0], iter + 1, n_fev + 1, TerminationReason::StepTolerance));
+        }
+    }
+    Err(SolverError::MaxIterations)
+}
+
+/// @brief Steffensen's method for solving a function f(x) = 0
+/// @param f function to solve
+/// @param x0 initial guess
+/// @param tol tolerance
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note The derivative of Newton's method is replaced by the slope (f(x + f(x)) - f(x))/f(x), so the method converges quadratically without any derivative, at the cost of two function evaluations per iteration.
+/// @note Like Newton's method, Steffensen's method requires a good initial guess, and is not guaranteed to converge. It also assumes that f(x) is small compared to the scale of the function.
+pub fn steffensen_solve<F>(f : F, x0 : f64, tol : f64, max_iter : u32) -> Result<SolverResult<f64>, SolverError>
+where F : Fn(f64) -> f64
+{
+    let mut x: f64 = x0;
+    let mut dx: f64;
+    let mut fx: f64 = f(x);
+    let mut fx_p_fx: f64;
+    let mut n_fev: u32 = 1;
+    for iter in 0..max_iter {
+        if !fx.is_finite() {
+            return Err(SolverError::NonFiniteValue);
+        }
+        if fx == 0.0 {
+            return Ok(SolverResult::new(x, fx, iter, n_fev, TerminationReason::ExactRoot));
+        }
+        fx_p_fx = f(x + fx); n_fev += 1;
+        if !fx_p_fx.is_finite() {
+            return Err(SolverError::NonFiniteValue);
+        }
+        if fx_p_fx == fx {
+            return Err(SolverError::ZeroDerivative);
+        }
+        dx = fx*fx/(fx_p_fx - fx);
+        x -= dx;
+        fx = f(x); n_fev += 1;
+        if f64::abs(dx) < tol {
+            return Ok(SolverResult::new(x, fx, iter + 1, n_fev, TerminationReason::StepTolerance));
+        }
+    }
+    Err(SolverError::MaxIterations)
+}
+
+/// Computes Laguerre's step for a function f(x) = 0 given f(x), f'(x) and f''(x)
+/// @param f_x value of the function at x
+/// @param df_x first derivative of the function at x
+/// @param ddf_x second derivative of the function at x
+/// @param n degree of the polynomial (or free parameter for general functions)
+/// @return step to subtract from x, or None if the denominator vanishes
+fn laguerre_step(f_x: f64, df_x: f64, ddf_x: f64, n: f64) -> Option<f64> {
+    let g: f64 = df_x/f_x;
+    let h: f64 = g.powi(2) - ddf_x/f_x;
+    // A negative discriminant means the nearest root is complex : only keep the real part of the step
+    let sq: f64 = f64::sqrt(f64::max((n - 1.0)*(n*h - g.powi(2)), 0.0));
+    // Choose the sign that maximises the magnitude of the denominator
+    let denom: f64 = if g >= 0.0 { g + sq } else { g - sq };
+    if denom == 0.0 {
+        return None;
+    }
+    Some(n/denom)
+}
+
+/// Laguerre's method for solving a function f(x) = 0
+/// @param f function to solve
+/// @param df derivative of function f
+/// @param ddf second derivative of function f
+/// @param x0 initial guess
+/// @param degree degree of the polynomial f. If None, a degree of 2 is used, which is suitable for general functions.
+/// @param tol tolerance
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note This method converges cubically towards simple roots of polynomials when the degree of the polynomial is given.
+#[allow(clippy::too_many_arguments)]
+pub fn laguerre_solve<F, F2, F3>(f: F, df: F2, ddf: F3, x0: f64, degree: Option<u32>, tol: f64, max_iter: u32, verbose: bool) -> Result<SolverResult<f64>, SolverError>
 where F : Fn(f64) -> f64, F2 : Fn(f64) -> f64, F3 : Fn(f64) -> f64
 {
+    if degree == Some(0) {
+        return Err(SolverError::InvalidInput("the degree of the polynomial must be at least 1"));
+    }
+    let n: f64 = degree.unwrap_or(2) as f64;
     let mut x: f64 = x0;
     let mut f_x: f64;
     let mut df_x: f64;
     let mut ddf_x: f64;
-    for _i in 0..max_iter {
-        f_x = f(x);
+    let mut n_fev: u32 = 0;
+    for i in 0..max_iter {
+        f_x = f(x); n_fev += 1;
         df_x = df(x);
         ddf_x = ddf(x);
         if verbose {
             println!("x = {}, f(x) = {}, df(x) = {}, ddf(x) = {}", x, f_x, df_x, ddf_x);
         }
+        if !f_x.is_finite() {
+            return Err(SolverError::NonFiniteValue);
+        }
         if f64::abs(f_x) < tol {
-            return Ok(x);
+            return Ok(SolverResult::new(x, f_x, i, n_fev, TerminationReason::FunctionTolerance));
+        }
+        match laguerre_step(f_x, df_x, ddf_x, n) {
+            Some(dx) => x -= dx,
+            None => return Err(SolverError::ZeroDerivative),
         }
-        x = x - 2.0*f_x*df_x / (2.0*df_x.powi(2) - f_x*ddf_x);
     }
-    return Err("Halley method did not converge after reaching the maximum number of iterations allowed.")
+    Err(SolverError::MaxIterations)
 }
 
-/// Halley's method for solving a function f(x) = 0
+/// Laguerre's method for solving a function f(x) = 0
 /// @param f function to solve
 /// @param x0 initial guess
+/// @param degree degree of the polynomial f. If None, a degree of 2 is used, which is suitable for general functions.
 /// @param tol tolerance
 /// @param dx_num numerical differentiation step size
 /// @param max_iter maximum number of iterations
-/// @return solution
-/// @note This method is more efficient than Newton's method, but requires the second derivative of f.
+/// @return solution report
+/// @note This method converges cubically towards simple roots of polynomials when the degree of the polynomial is given.
 /// @note This method uses numerical differentiation to compute the first and second derivatives.
-pub fn halley_solve_num<F>(f: F, x0: f64, tol: f64, dx_num : f64, max_iter: u32, verbose: bool) -> Result<f64, &'static str>
+pub fn laguerre_solve_num<F>(f: F, x0: f64, degree: Option<u32>, tol: f64, dx_num : f64, max_iter: u32, verbose: bool) -> Result<SolverResult<f64>, SolverError>
 where F : Fn(f64) -> f64
 {
+    if degree == Some(0) {
+        return Err(SolverError::InvalidInput("the degree of the polynomial must be at least 1"));
+    }
+    let n: f64 = degree.unwrap_or(2) as f64;
     let mut x: f64 = x0;
     let mut f_x: f64;
     let mut df_x: f64;
     let mut ddf_x: f64;
-    let mut fx_m_dx: f64;
-    let mut fx_p_dx: f64;
-    for _i in 0..max_iter {
-        f_x = f(x);
-        fx_m_dx = f(x - dx_num);
-        fx_p_dx = f(x + dx_num);
-        df_x = (fx_p_dx - fx_m_dx)/(2.0*dx_num);
-        ddf_x = (fx_p_dx - 2.0*f_x + fx_m_dx) / (dx_num.powi(2));
+    let mut n_fev: u32 = 0;
+    for i in 0..max_iter {
+        (f_x, df_x, ddf_x) = central_derivatives(&f, x, dx_num);
+        n_fev += 3;
         if verbose {
             println!("x = {}, f(x) = {}, df(x) = {}, ddf(x) = {}", x, f_x, df_x, ddf_x);
         }
+        if !f_x.is_finite() {
+            return Err(SolverError::NonFiniteValue);
+        }
         if f64::abs(f_x) < tol {
-            return Ok(x);
+            return Ok(SolverResult::new(x, f_x, i, n_fev, TerminationReason::FunctionTolerance));
+        }
+        match laguerre_step(f_x, df_x, ddf_x, n) {
+            Some(dx) => x -= dx,
+            None => return Err(SolverError::ZeroDerivative),
         }
-        x = x - 2.0*f_x*df_x / (2.0*df_x.powi(2) - f_x*ddf_x);
     }
-    return Err("Halley method did not converge after reaching the maximum number of iterations allowed.")
+    Err(SolverError::MaxIterations)
 }
 
 // --------------------------------------------------------------------
@@ -119,30 +308,48 @@ where F : Fn(f64) -> f64
 /// @param a left bracket
 /// @param b right bracket
 /// @param tol tolerance
-/// @return solution
+/// @return solution report
 /// @note The interval [a, b] must bracket the root, meaning f(a) and f(b) must be of a different sign.
-pub fn bisection_solve<F>(f : F, mut a : f64, mut b : f64, tol : f64) -> Result<f64, &'static str>
+pub fn bisection_solve<F>(f : F, mut a : f64, mut b : f64, tol : f64) -> Result<SolverResult<f64>, SolverError>
 where F : Fn(f64) -> f64
 {
     let mut c: f64;
     let mut fa: f64 = f(a);
     let mut fb: f64 = f(b);
     let mut fc: f64;
-    let max_iter: u32 = (f64::log2((b-a)/tol)).ceil() as u32;
-    for _iter in 0..max_iter {
+    let mut n_fev: u32 = 2;
+    if tol <= 0.0 {
+        return Err(SolverError::InvalidInput("the tolerance must be strictly positive"));
+    }
+    if !fa.is_finite() || !fb.is_finite() {
+        return Err(SolverError::NonFiniteValue);
+    }
+    if fa == 0.0 { return Ok(SolverResult::new(a, fa, 0, n_fev, TerminationReason::ExactRoot)); }
+    if fb == 0.0 { return Ok(SolverResult::new(b, fb, 0, n_fev, TerminationReason::ExactRoot)); }
+    if fa*fb > 0.0 {
+        return Err(SolverError::NotBracketed);
+    }
+    let max_iter: u32 = (f64::log2(f64::abs(b-a)/tol)).ceil().max(0.0) as u32;
+    for iter in 0..max_iter {
         c = (a + b)/2.0;
-        fc = f(c);
-        if fa*fc < 0.0 {
+        fc = f(c); n_fev += 1;
+        if !fc.is_finite() {
+            return Err(SolverError::NonFiniteValue);
+        } else if fc == 0.0 {
+            return Ok(SolverResult::new(c, fc, iter + 1, n_fev, TerminationReason::ExactRoot));
+        } else if fa*fc < 0.0 {
             b = c;
             fb = fc;
         } else if fb*fc < 0.0 {
             a = c;
             fa = fc;
         } else {
-            return Result::Err("The interval does not backet the root.");
+            return Result::Err(SolverError::NotBracketed);
         }
     }
-    return Result::Ok((a + b)/2.0);
+    c = (a + b)/2.0;
+    fc = f(c); n_fev += 1;
+    Result::Ok(SolverResult::new(c, fc, max_iter, n_fev, TerminationReason::StepTolerance))
 }
 
 /// @brief Secant method for solving a function f(x) = 0
@@ -151,29 +358,36 @@ where F : Fn(f64) -> f64
 /// @param b right bracket
 /// @param tol tolerance
 /// @param max_iter maximum number of iterations
-/// @return solution
+/// @return solution report
 /// @note The interval [a, b] does not have to bracket the root.
 /// @note The secant method is not guaranteed to converge.
-pub fn secant_solve<F>(f : F, mut a : f64, mut b : f64, tol : f64, max_iter : u32) -> f64
+pub fn secant_solve<F>(f : F, mut a : f64, mut b : f64, tol : f64, max_iter : u32) -> Result<SolverResult<f64>, SolverError>
 where F : Fn(f64) -> f64
 {
-    let mut c: f64 = (a + b)/2.0;
+    let mut c: f64;
     let mut fa: f64 = f(a);
     let mut fb: f64 = f(b);
     let mut fc: f64;
-    for _iter in 0..max_iter {
+    let mut n_fev: u32 = 2;
+    for iter in 0..max_iter {
+        if !fa.is_finite() || !fb.is_finite() {
+            return Err(SolverError::NonFiniteValue);
+        }
+        if fa == fb {
+            return Err(SolverError::ZeroDerivative);
+        }
         // c is x[n], a is x[n-1], b is x[n-2]
         c = a - fa*(a - b)/(fa - fb);
-        fc = f(c);
+        fc = f(c); n_fev += 1;
         b = a;
         fb = fa;
         a = c;
         fa = fc;
         if (b - a).abs() < tol {
-            break;
+            return Ok(SolverResult::new(c, fc, iter + 1, n_fev, TerminationReason::StepTolerance));
         }
     }
-    return c;
+    Err(SolverError::MaxIterations)
 }
 
 /// @brief Ridder's method for solving a function f(x) = 0
@@ -181,9 +395,10 @@ where F : Fn(f64) -> f64
 /// @param a left bracket
 /// @param b right bracket
 /// @param tol tolerance
-/// @return solution
+/// @param max_iter maximum number of iterations
+/// @return solution report
 /// @note The interval [a, b] must bracket the root, meaning f(a) and f(b) must be of a different sign.
-pub fn ridder_solve<F>(f : F, mut a : f64, mut b : f64, tol : f64, max_iter : u32) -> Result<f64, &'static str>
+pub fn ridder_solve<F>(f : F, mut a : f64, mut b : f64, tol : f64, max_iter : u32) -> Result<SolverResult<f64>, SolverError>
 where F : Fn(f64) -> f64
 {
     let mut fa: f64 = f(a);
@@ -195,14 +410,18 @@ where F : Fn(f64) -> f64
     let mut x: f64;
     let mut fx: f64;
     let mut x_old: f64 = (a + b)/2.0;
-    if fa == 0.0 { return Ok(a); }
-    if fb == 0.0 { return Ok(b); }
+    let mut n_fev: u32 = 2;
+    if fa == 0.0 { return Ok(SolverResult::new(a, fa, 0, n_fev, TerminationReason::ExactRoot)); }
+    if fb == 0.0 { return Ok(SolverResult::new(b, fb, 0, n_fev, TerminationReason::ExactRoot)); }
+    if !fa.is_finite() || !fb.is_finite() {
+        return Err(SolverError::NonFiniteValue);
+    }
     if fa*fb > 0.0 {
-        return Err("Root is not bracketed")
+        return Err(SolverError::NotBracketed);
     }
     for i in 0..max_iter {
         // Compute the improved root x from Ridder's formula
-        c = 0.5*(a + b); fc = f(c);
+        c = 0.5*(a + b); fc = f(c); n_fev += 1;
         s = f64::sqrt(fc.powi(2) - fa*fb);
         if s != 0.0 {
             dx = (c - a)*fc/s;
@@ -210,10 +429,14 @@ where F : Fn(f64) -> f64
             dx = (c - a)*fc;
         }
         if (fa - fb) < 0.0 { dx = -dx; }
-        x = c + dx; fx = f(x);
+        x = c + dx; fx = f(x); n_fev += 1;
+        if !fc.is_finite() || !fx.is_finite() {
+            return Err(SolverError::NonFiniteValue);
+        }
         // Test for convergence
+        if fx == 0.0 { return Ok(SolverResult::new(x, fx, i + 1, n_fev, TerminationReason::ExactRoot)) }
         if i > 0 {
-            if f64::abs(x - x_old) < tol*f64::max(f64::abs(x),1.0) { return Ok(x) }
+            if f64::abs(x - x_old) < tol*f64::max(f64::abs(x),1.0) { return Ok(SolverResult::new(x, fx, i + 1, n_fev, TerminationReason::StepTolerance)) }
         }
         x_old = x;
         // Re-bracket the root as tightly as possible
@@ -224,5 +447,678 @@ where F : Fn(f64) -> f64
             a = c; b = x; fa = fc; fb = fx;
         }
     }
-    return Err("Maximum number of iterations exceeded.")
-}
\ No newline at end of file
+    Err(SolverError::MaxIterations)
+}
+
+/// @brief Brent's method for solving a function f(x) = 0
+/// @param f function to solve
+/// @param a left bracket
+/// @param b right bracket
+/// @param tol tolerance
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note The interval [a, b] must bracket the root, meaning f(a) and f(b) must be of a different sign.
+/// @note This is the Brent-Dekker method : it combines bisection, secant and inverse quadratic interpolation steps, keeping the robustness of bisection and the superlinear convergence of the interpolation methods.
+pub fn brent_solve<F>(f : F, mut a : f64, mut b : f64, tol : f64, max_iter : u32) -> Result<SolverResult<f64>, SolverError>
+where F : Fn(f64) -> f64
+{
+    let mut fa: f64 = f(a);
+    let mut fb: f64 = f(b);
+    let n_fev: u32 = 2;
+    if fa == 0.0 { return Ok(SolverResult::new(a, fa, 0, n_fev, TerminationReason::ExactRoot)); }
+    if fb == 0.0 { return Ok(SolverResult::new(b, fb, 0, n_fev, TerminationReason::ExactRoot)); }
+    if !fa.is_finite() || !fb.is_finite() {
+        return Err(SolverError::NonFiniteValue);
+    }
+    if fa*fb > 0.0 {
+        return Err(SolverError::NotBracketed);
+    }
+    // b is the current best estimate, a is the previous one and c is the contrapoint (f(b) and f(c) have opposite signs)
+    let mut c: f64 = a;
+    let mut fc: f64 = fa;
+    let mut d: f64 = b - a;// Current step
+    let mut e: f64 = d;    // Previous step
+    let mut p: f64;
+    let mut q: f64;
+    let mut r: f64;
+    let mut s: f64;
+    let mut tol1: f64;
+    let mut xm: f64;
+    // One evaluation per iteration after the two of the bracket
+    for (iter, n_fev) in (0..max_iter).zip(n_fev..) {
+        if fb*fc > 0.0 {
+            // Rename a, b, c and adjust the bounding interval
+            c = a; fc = fa;
+            d = b - a; e = d;
+        }
+        if f64::abs(fc) < f64::abs(fb) {
+            // Make sure b is the best estimate so far
+            a = b; b = c; c = a;
+            fa = fb; fb = fc; fc = fa;
+        }
+        // Test for convergence
+        tol1 = 2.0*f64::EPSILON*f64::abs(b) + 0.5*tol;
+        xm = 0.5*(c - b);
+        if fb == 0.0 {
+            return Ok(SolverResult::new(b, fb, iter, n_fev, TerminationReason::ExactRoot));
+        }
+        if f64::abs(xm) <= tol1 {
+            return Ok(SolverResult::new(b, fb, iter, n_fev, TerminationReason::StepTolerance));
+        }
+        if f64::abs(e) >= tol1 && f64::abs(fa) > f64::abs(fb) {
+            // Attempt inverse quadratic interpolation (or secant step if only two points are distinct)
+            s = fb/fa;
+            if a == c {
+                p = 2.0*xm*s;
+                q = 1.0 - s;
+            } else {
+                q = fa/fc;
+                r = fb/fc;
+                p = s*(2.0*xm*q*(q - r) - (b - a)*(r - 1.0));
+                q = (q - 1.0)*(r - 1.0)*(s - 1.0);
+            }
+            if p > 0.0 { q = -q; }
+            p = f64::abs(p);
+            // Accept the interpolation only if it falls within the bounds and decreases fast enough
+            if 2.0*p < f64::min(3.0*xm*q - f64::abs(tol1*q), f64::abs(e*q)) {
+                e = d;
+                d = p/q;
+            } else {
+                // Interpolation failed, use bisection
+                d = xm;
+                e = d;
+            }
+        } else {
+            // Bounds decreasing too slowly, use bisection
+            d = xm;
+            e = d;
+        }
+        // Move the last best guess to a and evaluate the new trial root
+        a = b;
+        fa = fb;
+        if f64::abs(d) > tol1 {
+            b += d;
+        } else {
+            b += f64::copysign(tol1, xm);
+        }
+        fb = f(b);
+        if !fb.is_finite() {
+            return Err(SolverError::NonFiniteValue);
+        }
+    }
+    Err(SolverError::MaxIterations)
+}
+
+/// @brief ITP (Interpolate, Truncate and Project) method for solving a function f(x) = 0
+/// @param f function to solve
+/// @param a left bracket
+/// @param b right bracket
+/// @param tol tolerance : the returned root is within tol of the exact root
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note The interval [a, b] must bracket the root, meaning f(a) and f(b) must be of a different sign.
+/// @note The regula falsi estimate is truncated towards the midpoint and projected onto a ball around the midpoint, so that the method never needs more iterations than bisection while converging superlinearly on smooth functions (Oliveira & Takahashi, 2020).
+pub fn itp_solve<F>(f : F, mut a : f64, mut b : f64, tol : f64, max_iter : u32) -> Result<SolverResult<f64>, SolverError>
+where F : Fn(f64) -> f64
+{
+    // Parameters
+    let k2: f64 = 2.0;   // Order of the truncation, between 1 and 1 + phi
+    let n0: f64 = 1.0;   // Number of iterations allowed in addition to those of bisection
+
+    if tol <= 0.0 {
+        return Err(SolverError::InvalidInput("the tolerance must be strictly positive"));
+    }
+    (a, b) = (f64::min(a, b), f64::max(a, b));
+    let mut fa: f64 = f(a);
+    let mut fb: f64 = f(b);
+    let mut n_fev: u32 = 2;
+    if fa == 0.0 { return Ok(SolverResult::new(a, fa, 0, n_fev, TerminationReason::ExactRoot)); }
+    if fb == 0.0 { return Ok(SolverResult::new(b, fb, 0, n_fev, TerminationReason::ExactRoot)); }
+    if !fa.is_finite() || !fb.is_finite() {
+        return Err(SolverError::NonFiniteValue);
+    }
+    if fa*fb > 0.0 {
+        return Err(SolverError::NotBracketed);
+    }
+    let sign: f64 = fb.signum();// f is multiplied by sign so that f(a) < 0 < f(b)
+    let k1: f64 = 0.2/(b - a);
+    let n_half: f64 = f64::max(f64::log2((b - a)/(2.0*tol)).ceil(), 0.0);
+    let n_max: f64 = n_half + n0;
+
+    for iter in 0..max_iter {
+        if b - a <= 2.0*tol {
+            let x: f64 = 0.5*(a + b);
+            return Ok(SolverResult::new(x, f(x), iter, n_fev + 1, TerminationReason::StepTolerance));
+        }
+        let x_half: f64 = 0.5*(a + b);
+        let r: f64 = tol*f64::powf(2.0, n_max - iter as f64) - 0.5*(b - a);// Radius of the projection ball
+        let delta: f64 = f64::max(k1*(b - a).powf(k2), tol);// At least tol, so that a one-sided interpolation does not stall on an end of the bracket
+
+        // Interpolation
+        let x_f: f64 = (fb*a - fa*b)/(fb - fa);
+        // Truncation
+        let sigma: f64 = (x_half - x_f).signum();
+        let x_t: f64 = if delta <= (x_half - x_f).abs() { x_f + sigma*delta } else { x_half };
+        // Projection
+        let x: f64 = if (x_t - x_half).abs() <= r { x_t } else { x_half - sigma*r };
+
+        let fx: f64 = f(x); n_fev += 1;
+        if !fx.is_finite() {
+            return Err(SolverError::NonFiniteValue);
+        }
+        if fx == 0.0 {
+            return Ok(SolverResult::new(x, fx, iter + 1, n_fev, TerminationReason::ExactRoot));
+        } else if sign*fx > 0.0 {
+            b = x;
+            fb = fx;
+        } else {
+            a = x;
+            fa = fx;
+        }
+    }
+    if b - a <= 2.0*tol {
+        let x: f64 = 0.5*(a + b);
+        return Ok(SolverResult::new(x, f(x), max_iter, n_fev + 1, TerminationReason::StepTolerance));
+    }
+    Err(SolverError::MaxIterations)
+}
+
+/// Bracketing interval of the TOMS 748 method, with the points d and e discarded by the two previous steps.
+struct Toms748Bracket {
+    a: f64, b: f64, d: f64, e: f64,
+    fa: f64, fb: f64, fd: f64, fe: f64,
+}
+
+impl Toms748Bracket {
+    /// Evaluates f at c, safeguarded to lie inside [a, b], and shrinks the bracket to [a, c] or [c, b]. d receives the discarded end.
+    fn shrink<F>(&mut self, f: &F, c: f64, n_fev: &mut u32) -> Result<(), SolverError>
+    where F : Fn(f64) -> f64
+    {
+        let margin: f64 = f64::min(2.0*f64::EPSILON*f64::max(self.a.abs(), self.b.abs()), 0.25*(self.b - self.a));
+        let c: f64 = if c > self.a + margin && c < self.b - margin { c } else { 0.5*(self.a + self.b) };
+        let fc: f64 = f(c);
+        *n_fev += 1;
+        if !fc.is_finite() {
+            return Err(SolverError::NonFiniteValue);
+        }
+        if fc == 0.0 {
+            self.a = c; self.fa = 0.0;
+            self.d = 0.0; self.fd = 0.0;
+        } else if self.fa.signum()*fc.signum() < 0.0 {
+            self.d = self.b; self.fd = self.fb;
+            self.b = c; self.fb = fc;
+        } else {
+            self.d = self.a; self.fd = self.fa;
+            self.a = c; self.fa = fc;
+        }
+        Ok(())
+    }
+
+    /// Secant step between a and b.
+    fn secant(&self) -> f64 {
+        self.a - self.fa*(self.b - self.a)/(self.fb - self.fa)
+    }
+
+    /// Newton steps on the quadratic interpolating a, b and d, falling back to the secant step.
+    fn quadratic(&self, n_steps: u32) -> f64 {
+        let b_coef: f64 = (self.fb - self.fa)/(self.b - self.a);
+        let a_coef: f64 = ((self.fd - self.fb)/(self.d - self.b) - b_coef)/(self.d - self.a);
+        if a_coef == 0.0 || !a_coef.is_finite() {
+            return self.secant();
+        }
+        let mut c: f64 = if a_coef.signum()*self.fa.signum() > 0.0 { self.a } else { self.b };
+        for _ in 0..n_steps {
+            c -= (self.fa + (b_coef + a_coef*(c - self.b))*(c - self.a))/(b_coef + a_coef*(2.0*c - self.a - self.b));
+        }
+        if c > self.a && c < self.b { c } else { self.secant() }
+    }
+
+    /// Inverse cubic interpolation through a, b, d and e, falling back to the quadratic step.
+    fn cubic(&self) -> f64 {
+        let (a, b, d, e) = (self.a, self.b, self.d, self.e);
+        let (fa, fb, fd, fe) = (self.fa, self.fb, self.fd, self.fe);
+        let q11: f64 = (d - e)*fd/(fe - fd);
+        let q21: f64 = (b - d)*fb/(fd - fb);
+        let q31: f64 = (a - b)*fa/(fb - fa);
+        let d21: f64 = (b - d)*fd/(fd - fb);
+        let d31: f64 = (a - b)*fb/(fb - fa);
+        let q22: f64 = (d21 - q11)*fb/(fe - fb);
+        let q32: f64 = (d31 - q21)*fa/(fd - fa);
+        let d32: f64 = (d31 - q21)*fd/(fd - fa);
+        let q33: f64 = (d32 - q22)*fa/(fe - fa);
+        let c: f64 = a + q31 + q32 + q33;
+        if c > a && c < b { c } else { self.quadratic(3) }
+    }
+
+    /// Interpolation step : inverse cubic if the four function values are distinct, quadratic otherwise.
+    fn interpolate(&self, n_steps_quadratic: u32) -> f64 {
+        let fs: [f64; 4] = [self.fa, self.fb, self.fd, self.fe];
+        let distinct: bool = (0..4).all(|i| (i+1..4).all(|j| fs[i] != fs[j]));
+        if distinct { self.cubic() } else { self.quadratic(n_steps_quadratic) }
+    }
+}
+
+/// @brief TOMS 748 method of Alefeld, Potra and Shi for solving a function f(x) = 0
+/// @param f function to solve
+/// @param a left bracket
+/// @param b right bracket
+/// @param tol tolerance : the returned root is within tol of the exact root
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note The interval [a, b] must bracket the root, meaning f(a) and f(b) must be of a different sign.
+/// @note Each iteration performs two inverse cubic interpolation steps, a double-length secant step, and a bisection step if the bracket did not shrink by half. The method has the best asymptotic efficiency among bracketing methods, while the width of the bracket always converges to zero (Alefeld, Potra & Shi, 1995).
+pub fn toms748_solve<F>(f : F, a : f64, b : f64, tol : f64, max_iter : u32) -> Result<SolverResult<f64>, SolverError>
+where F : Fn(f64) -> f64
+{
+    // Parameters
+    let mu: f64 = 0.5;   // Smallest reduction of the bracket per iteration before a bisection step is taken
+
+    if tol <= 0.0 {
+        return Err(SolverError::InvalidInput("the tolerance must be strictly positive"));
+    }
+    let (a, b) = (f64::min(a, b), f64::max(a, b));
+    let fa: f64 = f(a);
+    let fb: f64 = f(b);
+    let mut n_fev: u32 = 2;
+    if fa == 0.0 { return Ok(SolverResult::new(a, fa, 0, n_fev, TerminationReason::ExactRoot)); }
+    if fb == 0.0 { return Ok(SolverResult::new(b, fb, 0, n_fev, TerminationReason::ExactRoot)); }
+    if !fa.is_finite() || !fb.is_finite() {
+        return Err(SolverError::NonFiniteValue);
+    }
+    if fa*fb > 0.0 {
+        return Err(SolverError::NotBracketed);
+    }
+
+    // Returns the best end of the bracket if the method has converged : the bracket must be at most tol wide, so that both ends are within tol of the root
+    let converged = |br: &Toms748Bracket, iter: u32, n_fev: u32| -> Option<SolverResult<f64>> {
+        if br.fa == 0.0 {
+            return Some(SolverResult::new(br.a, 0.0, iter, n_fev, TerminationReason::ExactRoot));
+        }
+        if br.b - br.a <= tol {
+            let (x, fx) = if br.fa.abs() < br.fb.abs() { (br.a, br.fa) } else { (br.b, br.fb) };
+            return Some(SolverResult::new(x, fx, iter, n_fev, TerminationReason::StepTolerance));
+        }
+        None
+    };
+
+    // The first two steps are a secant and a quadratic interpolation, which provide the points d and e
+    let mut br = Toms748Bracket { a, b, d: f64::NAN, e: f64::NAN, fa, fb, fd: f64::NAN, fe: f64::NAN };
+    br.shrink(&f, br.secant(), &mut n_fev)?;
+    if let Some(res) = converged(&br, 0, n_fev) { return Ok(res); }
+    let c: f64 = br.quadratic(2);
+    (br.e, br.fe) = (br.d, br.fd);
+    br.shrink(&f, c, &mut n_fev)?;
+
+    for iter in 0..max_iter {
+        if let Some(res) = converged(&br, iter, n_fev) { return Ok(res); }
+        let (a0, b0) = (br.a, br.b);
+
+        // Two interpolation steps
+        for n_steps_quadratic in [2, 3] {
+            let c: f64 = br.interpolate(n_steps_quadratic);
+            (br.e, br.fe) = (br.d, br.fd);
+            br.shrink(&f, c, &mut n_fev)?;
+            if let Some(res) = converged(&br, iter + 1, n_fev) { return Ok(res); }
+        }
+
+        // Double-length secant step from the end with the smallest |f|
+        let (u, fu) = if br.fa.abs() < br.fb.abs() { (br.a, br.fa) } else { (br.b, br.fb) };
+        let mut c: f64 = u - 2.0*fu/(br.fb - br.fa)*(br.b - br.a);
+        if (c - u).abs() > 0.5*(br.b - br.a) {
+            c = 0.5*(br.a + br.b);
+        }
+        (br.e, br.fe) = (br.d, br.fd);
+        br.shrink(&f, c, &mut n_fev)?;
+        if let Some(res) = converged(&br, iter + 1, n_fev) { return Ok(res); }
+
+        // Bisection step if the bracket did not shrink enough
+        if br.b - br.a >= mu*(b0 - a0) {
+            (br.e, br.fe) = (br.d, br.fd);
+            br.shrink(&f, 0.5*(br.a + br.b), &mut n_fev)?;
+        }
+    }
+    if let Some(res) = converged(&br, max_iter, n_fev) { return Ok(res); }
+    Err(SolverError::MaxIterations)
+}
+
+/// Modification of the function value at the retained end of the bracket in the regula falsi method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegulaFalsiVariant {
+    Illinois,       // f is halved
+    Pegasus,        // f is scaled by f_b/(f_b + f_c)
+    AndersonBjorck, // f is scaled by 1 - f_c/f_b, or halved if this factor is not positive
+}
+
+/// @brief Regula falsi (false position) method for solving a function f(x) = 0
+/// @param f function to solve
+/// @param a left bracket
+/// @param b right bracket
+/// @param method modification applied to the retained end of the bracket
+/// @param tol tolerance
+/// @param max_iter maximum number of iterations
+/// @return solution report
+/// @note The interval [a, b] must bracket the root, meaning f(a) and f(b) must be of a different sign.
+/// @note The plain regula falsi method keeps one end of the bracket fixed on convex functions and converges linearly. When the same end is retained twice in a row, its function value is scaled down so that the next secant step lands on the other side of the root, which makes the convergence superlinear.
+pub fn regula_falsi_solve<F>(f : F, mut a : f64, mut b : f64, method : RegulaFalsiVariant, tol : f64, max_iter : u32) -> Result<SolverResult<f64>, SolverError>
+where F : Fn(f64) -> f64
+{
+    // a is the retained end of the bracket, b the last iterate
+    let mut fa: f64 = f(a);
+    let mut fb: f64 = f(b);
+    let mut c: f64;
+    let mut fc: f64;
+    let mut n_fev: u32 = 2;
+    if fa == 0.0 { return Ok(SolverResult::new(a, fa, 0, n_fev, TerminationReason::ExactRoot)); }
+    if fb == 0.0 { return Ok(SolverResult::new(b, fb, 0, n_fev, TerminationReason::ExactRoot)); }
+    if !fa.is_finite() || !fb.is_finite() {
+        return Err(SolverError::NonFiniteValue);
+    }
+    if fa*fb > 0.0 {
+        return Err(SolverError::NotBracketed);
+    }
+    for iter in 0..max_iter {
+        // Secant step, which always lies inside the bracket
+        c = b - fb*(b - a)/(fb - fa);
+        fc = f(c); n_fev += 1;
+        if !fc.is_finite() {
+            return Err(SolverError::NonFiniteValue);
+        }
+        if fc == 0.0 { return Ok(SolverResult::new(c, fc, iter + 1, n_fev, TerminationReason::ExactRoot)); }
+        if fc*fb < 0.0 {
+            // The root lies between b and c : b becomes the retained end
+            a = b;
+            fa = fb;
+        } else {
+            // The root still lies between a and c : a is retained again and f(a) is scaled down
+            match method {
+                RegulaFalsiVariant::Illinois => {
+                    fa *= 0.5;
+                },
+                RegulaFalsiVariant::Pegasus => {
+                    fa *= fb/(fb + fc);
+                },
+                RegulaFalsiVariant::AndersonBjorck => {
+                    let m: f64 = 1.0 - fc/fb;
+                    fa *= if m > 0.0 { m } else { 0.5 };
+                },
+            }
+        }
+        // Test for convergence on the width of the bracket, since small steps may also come from a stalled end
+        b = c;
+        fb = fc;
+        if (b - a).abs() < tol {
+            return Ok(SolverResult::new(b, fb, iter + 1, n_fev, TerminationReason::StepTolerance));
+        }
+    }
+    Err(SolverError::MaxIterations)
+}
+
+// --------------------------------------------------------------------
+// -------------------------- Bracket search --------------------------
+// --------------------------------------------------------------------
+
+/// Interval [a, b] bracketing a root of a function : f(a) and f(b) are of a different sign, or one of them is zero.
+#[derive(Debug, Clone, Copy)]
+pub struct RootBracket {
+    pub a: f64,
+    pub b: f64,
+    pub f_a: f64,
+    pub f_b: f64,
+    pub n_fev: u32,    // number of function evaluations used to find the bracket
+}
+
+/// @brief Searches for an interval bracketing a root of a function f(x) by expanding an initial interval geometrically (zbrac of Numerical Recipes)
+/// @param f function to solve
+/// @param a initial guess
+/// @param b other end of the initial interval. If None, b = a + 0.1*max(1, |a|) is used.
+/// @return bracketing interval
+/// @note At each iteration, the end of the interval where |f| is the smallest is pushed outward by 1.6 times the width of the interval.
+pub fn bracket_root<F>(f : F, a : f64, b : Option<f64>) -> Result<RootBracket, SolverError>
+where F : Fn(f64) -> f64
+{
+    let factor: f64 = 1.6;
+    let max_iter: u32 = 50;
+
+    let mut a: f64 = a;
+    let mut b: f64 = b.unwrap_or(a + 0.1*f64::max(1.0, a.abs()));
+    if a == b {
+        return Err(SolverError::InvalidInput("the two ends of the initial interval must be different"));
+    }
+    let mut fa: f64 = f(a);
+    let mut fb: f64 = f(b);
+    for n_fev in 2..2 + max_iter {
+        if !fa.is_finite() || !fb.is_finite() {
+            return Err(SolverError::NonFiniteValue);
+        }
+        if fa*fb <= 0.0 {
+            return Ok(RootBracket { a, b, f_a: fa, f_b: fb, n_fev });
+        }
+        if fa.abs() < fb.abs() {
+            a += factor*(a - b);
+            fa = f(a);
+        } else {
+            b += factor*(b - a);
+            fb = f(b);
+        }
+    }
+    Err(SolverError::NotBracketed)
+}
+
+/// @brief Searches for all the intervals bracketing a root of a function f(x) by subdividing [a, b] into n equal sub-intervals (zbrak of Numerical Recipes)
+/// @param f function to solve
+/// @param a left end of the range
+/// @param b right end of the range
+/// @param n number of sub-intervals
+/// @return sub-intervals over which f changes sign, in increasing order. They all report the n + 1 function evaluations of the scan.
+/// @note Roots closer to each other than (b - a)/n may be missed, as well as roots of even multiplicity.
+pub fn subdivide_root_brackets<F>(f : F, a : f64, b : f64, n : u32) -> Result<Vec<RootBracket>, SolverError>
+where F : Fn(f64) -> f64
+{
+    if n == 0 {
+        return Err(SolverError::InvalidInput("the range must be divided into at least one sub-interval"));
+    }
+    let (a, b) = (f64::min(a, b), f64::max(a, b));
+    let h: f64 = (b - a)/(n as f64);
+    let xs: Vec<f64> = (0..=n).map(|i| if i == n { b } else { a + (i as f64)*h }).collect();
+    let fs: Vec<f64> = xs.iter().map(|&x| f(x)).collect();
+    if fs.iter().any(|fx| !fx.is_finite()) {
+        return Err(SolverError::NonFiniteValue);
+    }
+
+    let mut brackets: Vec<RootBracket> = Vec::new();
+    for i in 1..=(n as usize) {
+        if fs[i-1]*fs[i] < 0.0 || fs[i] == 0.0 || (i == 1 && fs[0] == 0.0) {
+            brackets.push(RootBracket { a: xs[i-1], b: xs[i], f_a: fs[i-1], f_b: fs[i], n_fev: n + 1 });
+        }
+    }
+    if brackets.is_empty() {
+        return Err(SolverError::NotBracketed);
+    }
+    Ok(brackets)
+}
+
+/// @brief Bisection method for solving a function f(x) = 0, without a bracket of the root
+/// @param f function to solve
+/// @param a initial guess
+/// @param b other end of the initial interval. If None, b = a + 0.1*max(1, |a|) is used.
+/// @param tol tolerance
+/// @return solution report, whose evaluation count includes the bracket search
+/// @note The root is first bracketed with bracket_root, then refined with bisection_solve.
+pub fn bisection_solve_auto<F>(f : F, a : f64, b : Option<f64>, tol : f64) -> Result<SolverResult<f64>, SolverError>
+where F : Fn(f64) -> f64
+{
+    let bracket: RootBracket = bracket_root(&f, a, b)?;
+    let mut res: SolverResult<f64> = bisection_solve(&f, bracket.a, bracket.b, tol)?;
+    res.n_fev += bracket.n_fev;
+    Ok(res)
+}
+
+/// @brief Ridder's method for solving a function f(x) = 0, without a bracket of the root
+/// @param f function to solve
+/// @param a initial guess
+/// @param b other end of the initial interval. If None, b = a + 0.1*max(1, |a|) is used.
+/// @param tol tolerance
+/// @param max_iter maximum number of iterations
+/// @return solution report, whose evaluation count includes the bracket search
+/// @note The root is first bracketed with bracket_root, then refined with ridder_solve.
+pub fn ridder_solve_auto<F>(f : F, a : f64, b : Option<f64>, tol : f64, max_iter : u32) -> Result<SolverResult<f64>, SolverError>
+where F : Fn(f64) -> f64
+{
+    let bracket: RootBracket = bracket_root(&f, a, b)?;
+    let mut res: SolverResult<f64> = ridder_solve(&f, bracket.a, bracket.b, tol, max_iter)?;
+    res.n_fev += bracket.n_fev;
+    Ok(res)
+}
+
+/// @brief Brent's method for solving a function f(x) = 0, without a bracket of the root
+/// @param f function to solve
+/// @param a initial guess
+/// @param b other end of the initial interval. If None, b = a + 0.1*max(1, |a|) is used.
+/// @param tol tolerance
+/// @param max_iter maximum number of iterations
+/// @return solution report, whose evaluation count includes the bracket search
+/// @note The root is first bracketed with bracket_root, then refined with brent_solve.
+pub fn brent_solve_auto<F>(f : F, a : f64, b : Option<f64>, tol : f64, max_iter : u32) -> Result<SolverResult<f64>, SolverError>
+where F : Fn(f64) -> f64
+{
+    let bracket: RootBracket = bracket_root(&f, a, b)?;
+    let mut res: SolverResult<f64> = brent_solve(&f, bracket.a, bracket.b, tol, max_iter)?;
+    res.n_fev += bracket.n_fev;
+    Ok(res)
+}
+
+// --------------------------------------------------------------------
+// ---------------------------- All roots -----------------------------
+// --------------------------------------------------------------------
+
+/// Root found by all_roots.
+#[derive(Debug, Clone, Copy)]
+pub struct RootEstimate {
+    pub x: f64,              // root
+    pub f_x: f64,            // function value at the root
+    pub multiplicity: u32,   // estimated multiplicity : odd if f changes sign at the root, even if f only touches zero
+}
+
+/// Recursively samples [a, b] where f dips towards zero without changing sign, since two close roots may be hidden between the samples.
+fn sample_dips<F>(f: &F, a: f64, f_a: f64, b: f64, f_b: f64, depth: u32, samples: &mut Vec<(f64, f64)>)
+where F : Fn(f64) -> f64
+{
+    let m: f64 = 0.5*(a + b);
+    let f_m: f64 = f(m);
+    samples.push((m, f_m));
+    if depth > 0 && f_a*f_m > 0.0 && f_m*f_b > 0.0 && f_m.abs() < f64::min(f_a.abs(), f_b.abs()) {
+        sample_dips(f, a, f_a, m, f_m, depth - 1, samples);
+        sample_dips(f, m, f_m, b, f_b, depth - 1, samples);
+    }
+}
+
+/// Estimates the multiplicity m of a root r from the growth of |f| away from it, |f(r + h)| ~ C*h^m.
+/// @param odd true if f changes sign at the root, in which case the estimate is rounded to an odd integer, and to an even integer otherwise
+fn root_multiplicity<F>(f: &F, r: f64, h: f64, odd: bool) -> u32
+where F : Fn(f64) -> f64
+{
+    let ratio: f64 = (f(r + 2.0*h).abs() + f(r - 2.0*h).abs())/(f(r + h).abs() + f(r - h).abs());
+    let m: f64 = if ratio.is_finite() && ratio > 1.0 { ratio.log2() } else { 1.0 };
+    let parity: f64 = if odd { 1.0 } else { 0.0 };
+    let m_rounded: f64 = 2.0*((m - parity)/2.0).round() + parity;
+    f64::max(m_rounded, if odd { 1.0 } else { 2.0 }) as u32
+}
+
+/// @brief Finds all the roots of a function f(x) over the interval [a, b]
+/// @param f function to solve
+/// @param a left end of the interval
+/// @param b right end of the interval
+/// @param n_samples number of sub-intervals of the initial uniform sampling
+/// @param tol tolerance on the roots, also used as the largest |f| for which a local minimum of |f| is reported as a root
+/// @param max_iter maximum number of iterations of the refinement of each root
+/// @return roots in increasing order, with duplicates removed
+/// @note Sign changes between samples are refined with Ridder's method. Samples are added where |f| dips towards zero without changing sign, and the local minima of |f| are refined with Brent's method, which reveals pairs of close roots and roots of even multiplicity (f tangent to zero).
+/// @note Roots closer to each other than the sampling step may still be missed, so n_samples should be large enough to resolve the oscillations of f.
+/// @note If Ridder's method fails on a bracket, the bracket is refined by bisection instead. A bracket that cannot be refined at all is skipped without discarding the other roots.
+pub fn all_roots<F>(f : F, a : f64, b : f64, n_samples : u32, tol : f64, max_iter : u32) -> Result<Vec<RootEstimate>, SolverError>
+where F : Fn(f64) -> f64
+{
+    let max_depth: u32 = 6;// Largest number of bisections of a sub-interval where f dips towards zero
+
+    if n_samples == 0 {
+        return Err(SolverError::InvalidInput("the interval must be divided into at least one sub-interval"));
+    }
+    if tol <= 0.0 {
+        return Err(SolverError::InvalidInput("the tolerance must be strictly positive"));
+    }
+    let (a, b) = (f64::min(a, b), f64::max(a, b));
+    let h: f64 = (b - a)/(n_samples as f64);
+
+    // Uniform sampling, refined where f dips towards zero
+    let mut samples: Vec<(f64, f64)> = (0..=n_samples).map(|i| {
+        let x: f64 = if i == n_samples { b } else { a + (i as f64)*h };
+        (x, f(x))
+    }).collect();
+    if samples.iter().any(|(_, fx)| !fx.is_finite()) {
+        return Err(SolverError::NonFiniteValue);
+    }
+    for i in 0..(n_samples as usize) {
+        let ((x0, f0), (x1, f1)) = (samples[i], samples[i+1]);
+        sample_dips(&f, x0, f0, x1, f1, max_depth, &mut samples);
+    }
+    samples.sort_by(|s1, s2| s1.0.total_cmp(&s2.0));
+    if samples.iter().any(|(_, fx)| !fx.is_finite()) {
+        return Err(SolverError::NonFiniteValue);
+    }
+
+    // Refinement of a sign change with Ridder's method, falling back to bisection if it fails, so that one difficult bracket does not discard the other roots
+    let refine = |x0: f64, x1: f64| -> Option<f64> {
+        match ridder_solve(&f, x0, x1, tol, max_iter) {
+            Ok(res) => Some(res.x),
+            Err(_) => bisection_solve(&f, x0, x1, tol).ok().map(|res| res.x),
+        }
+    };
+
+    // Exact zeros and sign changes between samples
+    let mut roots: Vec<(f64, bool)> = Vec::new();// Roots, and whether f changes sign there
+    for (i, &(x, fx)) in samples.iter().enumerate() {
+        if fx == 0.0 {
+            // f changes sign if the closest non-zero samples on each side have opposite signs (probed just outside of the interval at its ends)
+            let f_left: f64 = samples[..i].iter().rev().map(|s| s.1).find(|&v| v != 0.0).unwrap_or_else(|| f(x - 1e-3*h));
+            let f_right: f64 = samples[i+1..].iter().map(|s| s.1).find(|&v| v != 0.0).unwrap_or_else(|| f(x + 1e-3*h));
+            roots.push((x, f_left*f_right < 0.0));
+        } else if i + 1 < samples.len() && fx*samples[i+1].1 < 0.0 {
+            if let Some(x_root) = refine(x, samples[i+1].0) {
+                roots.push((x_root, true));
+            }
+        }
+    }
+
+    // Local minima of |f| without sign change : two close roots, or a root of even multiplicity
+    for i in 1..samples.len()-1 {
+        let ((x0, f0), (_, f1), (x2, f2)) = (samples[i-1], samples[i], samples[i+1]);
+        if f0*f1 > 0.0 && f1*f2 > 0.0 && f1.abs() <= f0.abs() && f1.abs() <= f2.abs() {
+            let sign: f64 = f1.signum();
+            let x_min: f64 = match univariate_minimizers::brent_minimize(|x: f64| sign*f(x), x0, x2, tol) {
+                Ok(res) => res.x,
+                Err(_) => continue,
+            };
+            let f_min: f64 = f(x_min);
+            if f_min*sign < 0.0 {
+                roots.extend([refine(x0, x_min), refine(x_min, x2)].into_iter().flatten().map(|x_root| (x_root, true)));
+            } else if f_min.abs() < tol {
+                roots.push((x_min, false));
+            }
+        }
+    }
+
+    // Sort the roots, remove the duplicates and estimate their multiplicity
+    roots.sort_by(|r1, r2| r1.0.total_cmp(&r2.0));
+    let mut result: Vec<RootEstimate> = Vec::new();
+    for (x, odd) in roots {
+        let dedup_tol: f64 = f64::max(10.0*tol, f64::EPSILON.sqrt()*x.abs());// Minima of |f| are only located to about sqrt(eps)
+        if let Some(last) = result.last() {
+            if (x - last.x).abs() <= dedup_tol {
+                continue;
+            }
+        }
+        let multiplicity: u32 = root_multiplicity(&f, x, 1e-3*h, odd);
+        result.push(RootEstimate { x, f_x: f(x), multiplicity });
+    }
+    Ok(result)
+}
@@ -40,7 +40,7 @@ impl Xorwow {
         self.z = self.w;
         self.w = self.v;
         self.v = (self.v ^ (self.v << 4)) ^ (t ^ (t << 1));
-        self.d += 362437;
+        self.d = self.d.wrapping_add(362437);
         self.wrapping_add(self.v ^ self.d)
     }
 